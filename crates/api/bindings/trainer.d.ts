@@ -0,0 +1,80 @@
+// Generated from the api crate models, do not edit.
+
+export type ExerciseType = "barbell" | "kettlebell" | "bodyweight";
+
+export interface Exercise {
+    id: number | null;
+    name: string;
+    description: string | null;
+    exercise_type: ExerciseType;
+    /**
+     * Weight of the empty bar, which no load can go below
+     */
+    bar_weight?: string | null;
+    /**
+     * Loads are rounded to a multiple of this, e.g. 2.5kg or 5lb, so they can be made up with
+     * the plates available
+     */
+    load_increment?: string | null;
+}
+
+/**
+ * Another name an exercise is known by, e.g. the name another app uses for it.  Aliases are
+ * remembered so importers resolve the name the same way every time.
+ */
+export interface ExerciseAlias {
+    alias: string;
+    exercise_id: number;
+}
+
+/**
+ * A training session on a given day, e.g. the Monday heavy day of a program.
+ */
+export interface WorkoutSession {
+    id: number | null;
+    /**
+     * Day the session was trained on, serialized as e.g. `"2024-05-01"`
+     */
+    date: string;
+    name: string | null;
+    notes: string | null;
+    /**
+     * The exercises performed, in the order they were done
+     */
+    exercises?: PerformedExercise[];
+    /**
+     * Numeric measurements the user tracks beyond the built in fields, keyed by a name of their
+     * choosing, e.g. `bodyweight_kg` or `sleep_hours`
+     */
+    metrics?: Record<string, number>;
+}
+
+/**
+ * An exercise done during a [`WorkoutSession`].
+ */
+export interface PerformedExercise {
+    exercise_id: number;
+    notes: string | null;
+}
+
+/**
+ * A completed set of an exercise performed during a [`WorkoutSession`].
+ */
+export interface Set {
+    id: number | null;
+    session_id: number;
+    exercise_id: number;
+    /**
+     * Position of the set among the sets of its exercise in the session, counting from 1
+     */
+    set_number: number;
+    /**
+     * The load lifted, `None` for a set without added weight, e.g. of pull-ups
+     */
+    weight?: string | null;
+    reps: number;
+    /**
+     * Rate of perceived exertion, from 1 to 10
+     */
+    rpe?: number | null;
+}
@@ -0,0 +1,239 @@
+//! Stable codes for every domain error, so clients can tell errors apart without parsing their
+//! messages.  Codes are only ever added; a released code keeps its meaning and spelling.
+
+/// Machine readable code of an error, serialized as e.g. `"EXERCISE_NOT_FOUND"`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "SCREAMING_SNAKE_CASE"))]
+#[non_exhaustive]
+pub enum TrainerErrorCode {
+    ExerciseNotFound,
+    LookupFailed,
+    SaveFailed,
+    DeleteFailed,
+    RestoreFailed,
+    InvalidExerciseType,
+    InvalidWeight,
+    PersistenceFailed,
+    ConnectionFailed,
+    QueryFailed,
+    DuplicateId,
+    SchemaMismatch,
+    MissingColumn,
+    InvalidMapping,
+    InvalidCsv,
+    InvalidJson,
+    MissingDatabase,
+    UnknownProfile,
+    ConfigValueNotSet,
+    InvalidConfig,
+    IntegrityCheckFailed,
+    IoError,
+    Unknown,
+    NoExerciseSelected,
+    SessionNotFound,
+    SetNotFound,
+    DuplicateName,
+    NotFound,
+    NoTerminal,
+    NotifyFailed,
+    InvalidRpe,
+}
+
+impl TrainerErrorCode {
+    pub const ALL: [TrainerErrorCode; 31] = [
+        TrainerErrorCode::ExerciseNotFound,
+        TrainerErrorCode::LookupFailed,
+        TrainerErrorCode::SaveFailed,
+        TrainerErrorCode::DeleteFailed,
+        TrainerErrorCode::RestoreFailed,
+        TrainerErrorCode::InvalidExerciseType,
+        TrainerErrorCode::InvalidWeight,
+        TrainerErrorCode::PersistenceFailed,
+        TrainerErrorCode::ConnectionFailed,
+        TrainerErrorCode::QueryFailed,
+        TrainerErrorCode::DuplicateId,
+        TrainerErrorCode::SchemaMismatch,
+        TrainerErrorCode::MissingColumn,
+        TrainerErrorCode::InvalidMapping,
+        TrainerErrorCode::InvalidCsv,
+        TrainerErrorCode::InvalidJson,
+        TrainerErrorCode::MissingDatabase,
+        TrainerErrorCode::UnknownProfile,
+        TrainerErrorCode::ConfigValueNotSet,
+        TrainerErrorCode::InvalidConfig,
+        TrainerErrorCode::IntegrityCheckFailed,
+        TrainerErrorCode::IoError,
+        TrainerErrorCode::Unknown,
+        TrainerErrorCode::NoExerciseSelected,
+        TrainerErrorCode::SessionNotFound,
+        TrainerErrorCode::SetNotFound,
+        TrainerErrorCode::DuplicateName,
+        TrainerErrorCode::NotFound,
+        TrainerErrorCode::NoTerminal,
+        TrainerErrorCode::NotifyFailed,
+        TrainerErrorCode::InvalidRpe,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TrainerErrorCode::ExerciseNotFound => "EXERCISE_NOT_FOUND",
+            TrainerErrorCode::LookupFailed => "LOOKUP_FAILED",
+            TrainerErrorCode::SaveFailed => "SAVE_FAILED",
+            TrainerErrorCode::DeleteFailed => "DELETE_FAILED",
+            TrainerErrorCode::RestoreFailed => "RESTORE_FAILED",
+            TrainerErrorCode::InvalidExerciseType => "INVALID_EXERCISE_TYPE",
+            TrainerErrorCode::InvalidWeight => "INVALID_WEIGHT",
+            TrainerErrorCode::PersistenceFailed => "PERSISTENCE_FAILED",
+            TrainerErrorCode::ConnectionFailed => "CONNECTION_FAILED",
+            TrainerErrorCode::QueryFailed => "QUERY_FAILED",
+            TrainerErrorCode::DuplicateId => "DUPLICATE_ID",
+            TrainerErrorCode::SchemaMismatch => "SCHEMA_MISMATCH",
+            TrainerErrorCode::MissingColumn => "MISSING_COLUMN",
+            TrainerErrorCode::InvalidMapping => "INVALID_MAPPING",
+            TrainerErrorCode::InvalidCsv => "INVALID_CSV",
+            TrainerErrorCode::InvalidJson => "INVALID_JSON",
+            TrainerErrorCode::MissingDatabase => "MISSING_DATABASE",
+            TrainerErrorCode::UnknownProfile => "UNKNOWN_PROFILE",
+            TrainerErrorCode::ConfigValueNotSet => "CONFIG_VALUE_NOT_SET",
+            TrainerErrorCode::InvalidConfig => "INVALID_CONFIG",
+            TrainerErrorCode::IntegrityCheckFailed => "INTEGRITY_CHECK_FAILED",
+            TrainerErrorCode::IoError => "IO_ERROR",
+            TrainerErrorCode::Unknown => "UNKNOWN",
+            TrainerErrorCode::NoExerciseSelected => "NO_EXERCISE_SELECTED",
+            TrainerErrorCode::SessionNotFound => "SESSION_NOT_FOUND",
+            TrainerErrorCode::SetNotFound => "SET_NOT_FOUND",
+            TrainerErrorCode::DuplicateName => "DUPLICATE_NAME",
+            TrainerErrorCode::NotFound => "NOT_FOUND",
+            TrainerErrorCode::NoTerminal => "NO_TERMINAL",
+            TrainerErrorCode::NotifyFailed => "NOTIFY_FAILED",
+            TrainerErrorCode::InvalidRpe => "INVALID_RPE",
+        }
+    }
+
+    /// Exit status of a process failing with this code.  Codes are grouped by tens: exercises
+    /// from 10, storage from 20, transfers from 30, configuration from 40, workouts from 50 and
+    /// the terminal from 60, leaving 1 for unknown errors and 2 for usage errors.  Like the
+    /// codes, a released status never changes.
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            TrainerErrorCode::Unknown => 1,
+            TrainerErrorCode::ExerciseNotFound => 10,
+            TrainerErrorCode::NoExerciseSelected => 11,
+            TrainerErrorCode::LookupFailed => 12,
+            TrainerErrorCode::SaveFailed => 13,
+            TrainerErrorCode::DeleteFailed => 14,
+            TrainerErrorCode::RestoreFailed => 15,
+            TrainerErrorCode::InvalidExerciseType => 16,
+            TrainerErrorCode::InvalidWeight => 17,
+            TrainerErrorCode::DuplicateName => 18,
+            TrainerErrorCode::PersistenceFailed => 20,
+            TrainerErrorCode::ConnectionFailed => 21,
+            TrainerErrorCode::QueryFailed => 22,
+            TrainerErrorCode::DuplicateId => 23,
+            TrainerErrorCode::SchemaMismatch => 24,
+            TrainerErrorCode::IntegrityCheckFailed => 25,
+            TrainerErrorCode::NotFound => 26,
+            TrainerErrorCode::MissingColumn => 30,
+            TrainerErrorCode::InvalidMapping => 31,
+            TrainerErrorCode::InvalidCsv => 32,
+            TrainerErrorCode::InvalidJson => 33,
+            TrainerErrorCode::IoError => 34,
+            TrainerErrorCode::MissingDatabase => 40,
+            TrainerErrorCode::UnknownProfile => 41,
+            TrainerErrorCode::ConfigValueNotSet => 42,
+            TrainerErrorCode::InvalidConfig => 43,
+            TrainerErrorCode::SessionNotFound => 50,
+            TrainerErrorCode::SetNotFound => 51,
+            TrainerErrorCode::InvalidRpe => 52,
+            TrainerErrorCode::NoTerminal => 60,
+            TrainerErrorCode::NotifyFailed => 61,
+        }
+    }
+}
+
+impl std::fmt::Display for TrainerErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn all_lists_every_code() {
+        // The match has no wildcard, so a new code doesn't compile until it is given the next
+        // position here and added to ALL in that place
+        fn position(code: TrainerErrorCode) -> usize {
+            match code {
+                TrainerErrorCode::ExerciseNotFound => 0,
+                TrainerErrorCode::LookupFailed => 1,
+                TrainerErrorCode::SaveFailed => 2,
+                TrainerErrorCode::DeleteFailed => 3,
+                TrainerErrorCode::RestoreFailed => 4,
+                TrainerErrorCode::InvalidExerciseType => 5,
+                TrainerErrorCode::InvalidWeight => 6,
+                TrainerErrorCode::PersistenceFailed => 7,
+                TrainerErrorCode::ConnectionFailed => 8,
+                TrainerErrorCode::QueryFailed => 9,
+                TrainerErrorCode::DuplicateId => 10,
+                TrainerErrorCode::SchemaMismatch => 11,
+                TrainerErrorCode::MissingColumn => 12,
+                TrainerErrorCode::InvalidMapping => 13,
+                TrainerErrorCode::InvalidCsv => 14,
+                TrainerErrorCode::InvalidJson => 15,
+                TrainerErrorCode::MissingDatabase => 16,
+                TrainerErrorCode::UnknownProfile => 17,
+                TrainerErrorCode::ConfigValueNotSet => 18,
+                TrainerErrorCode::InvalidConfig => 19,
+                TrainerErrorCode::IntegrityCheckFailed => 20,
+                TrainerErrorCode::IoError => 21,
+                TrainerErrorCode::Unknown => 22,
+                TrainerErrorCode::NoExerciseSelected => 23,
+                TrainerErrorCode::SessionNotFound => 24,
+                TrainerErrorCode::SetNotFound => 25,
+                TrainerErrorCode::DuplicateName => 26,
+                TrainerErrorCode::NotFound => 27,
+                TrainerErrorCode::NoTerminal => 28,
+                TrainerErrorCode::NotifyFailed => 29,
+                TrainerErrorCode::InvalidRpe => 30,
+            }
+        }
+        for (i, code) in TrainerErrorCode::ALL.iter().enumerate() {
+            assert_eq!(position(*code), i, "{}", code);
+        }
+    }
+
+    #[test]
+    fn codes_unique() {
+        let codes: HashSet<&str> = TrainerErrorCode::ALL.iter().map(|c| c.as_str()).collect();
+        assert_eq!(codes.len(), TrainerErrorCode::ALL.len());
+    }
+
+    #[test]
+    fn exit_codes_unique() {
+        let codes: HashSet<u8> = TrainerErrorCode::ALL
+            .iter()
+            .map(|c| c.exit_code())
+            .collect();
+        assert_eq!(codes.len(), TrainerErrorCode::ALL.len());
+        assert!(!codes.contains(&0));
+        assert!(!codes.contains(&2));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_matches_as_str() {
+        for code in TrainerErrorCode::ALL {
+            let json = serde_json::to_string(&code).unwrap();
+            assert_eq!(json, format!("\"{}\"", code));
+            assert_eq!(
+                serde_json::from_str::<TrainerErrorCode>(&json).unwrap(),
+                code
+            );
+        }
+    }
+}
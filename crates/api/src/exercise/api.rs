@@ -1,6 +1,6 @@
 use crate::exercise::error;
 use crate::repository::ExerciseRepository;
-use crate::{Exercise, ExerciseError, RepositoryError};
+use crate::{Exercise, ExerciseAlias, ExerciseError, RepositoryError};
 use async_trait::async_trait;
 use error::ExerciseResult;
 use tracing::{debug, error, instrument};
@@ -14,7 +14,21 @@ pub trait ExerciseManagement {
 
     async fn list(&self) -> ExerciseResult<Vec<Exercise>>;
 
+    // At most `limit` exercises whose name starts with `prefix`, for autocompletion
+    async fn autocomplete(&self, prefix: String, limit: u32) -> ExerciseResult<Vec<Exercise>>;
+
     async fn delete(&self, name: String) -> ExerciseResult<()>;
+
+    // Lists the deleted exercises by name
+    async fn deleted(&self) -> ExerciseResult<Vec<Exercise>>;
+
+    // Restores the deleted exercise called `name` (case-insensitive)
+    async fn restore(&self, name: String) -> ExerciseResult<()>;
+
+    // Remembers `alias` as another name of the exercise called `name`
+    async fn add_alias(&self, name: String, alias: String) -> ExerciseResult<()>;
+
+    async fn aliases(&self) -> ExerciseResult<Vec<ExerciseAlias>>;
 }
 
 #[derive(Clone, Debug)]
@@ -37,6 +51,10 @@ impl<'a, T: ExerciseRepository> ExerciseManager<'a, T> {
                 Ok(())
             }
             Err(err) => match err {
+                RepositoryError::DuplicateNameError(name) => {
+                    debug!("an exercise named {} already exists", name);
+                    Err(ExerciseError::DuplicateNameError)
+                }
                 RepositoryError::PersistenceError(err) => {
                     error!("{}", err);
                     Err(ExerciseError::SaveFailed)
@@ -79,6 +97,10 @@ impl<T: ExerciseRepository + Sync + std::fmt::Debug> ExerciseManagement for Exer
                             Ok(())
                         }
                         Err(err) => match err {
+                            RepositoryError::DuplicateNameError(name) => {
+                                debug!("an exercise named {} already exists", name);
+                                Err(ExerciseError::DuplicateNameError)
+                            }
                             RepositoryError::PersistenceError(e) => {
                                 error!("{}", e.to_string());
                                 Err(ExerciseError::SaveFailed)
@@ -149,6 +171,22 @@ impl<T: ExerciseRepository + Sync + std::fmt::Debug> ExerciseManagement for Exer
         }
     }
 
+    ///Retrieves the exercises whose name starts with `prefix` (case-insensitive)
+    ///
+    ///# Returns
+    ///* [`Ok`]` with at most `limit` exercises ordered by name
+    ///* A [`TrainerError::QueryError`] if there is a problem retrieving the exercises
+    #[instrument(skip(self))]
+    async fn autocomplete(&self, prefix: String, limit: u32) -> ExerciseResult<Vec<Exercise>> {
+        match self.repo.query_by_prefix(prefix, limit).await {
+            Ok(exercises) => Ok(exercises),
+            Err(err) => {
+                error!("{}", err.to_string());
+                Err(ExerciseError::LookupError)
+            }
+        }
+    }
+
     ///Deletes the exercise from the repository
     /// # Arguments
     /// * `name`: The name of the exercise to delete
@@ -184,6 +222,85 @@ impl<T: ExerciseRepository + Sync + std::fmt::Debug> ExerciseManagement for Exer
             },
         }
     }
+
+    ///Retrieves the exercises that have been deleted
+    #[instrument(skip(self))]
+    async fn deleted(&self) -> ExerciseResult<Vec<Exercise>> {
+        match self.repo.list_deleted().await {
+            Ok(exercises) => Ok(exercises),
+            Err(err) => {
+                error!("{}", err.to_string());
+                Err(ExerciseError::LookupError)
+            }
+        }
+    }
+
+    ///Restores a deleted exercise
+    /// # Arguments
+    /// * `name`: The name of the deleted exercise
+    /// # Returns
+    /// * [`Ok`] if the exercise was restored
+    /// * [`TrainerError::ExerciseNotFound`] if there is no deleted exercise with the name
+    /// * [`TrainerError::RestoreFailed`] if there was a problem restoring the exercise
+    #[instrument(skip(self), fields(name = name))]
+    async fn restore(&self, name: String) -> ExerciseResult<()> {
+        let deleted = self.deleted().await?;
+        let Some(id) = deleted
+            .iter()
+            .find(|e| e.name.eq_ignore_ascii_case(&name))
+            .and_then(|e| e.id)
+        else {
+            debug!("deleted exercise not found");
+            return Err(ExerciseError::ExerciseNotFoundError);
+        };
+        match self.repo.restore(id).await {
+            Ok(_) => Ok(()),
+            Err(RepositoryError::ItemNotFoundError) => Err(ExerciseError::ExerciseNotFoundError),
+            Err(err) => {
+                error!("{}", err.to_string());
+                Err(ExerciseError::RestoreFailed)
+            }
+        }
+    }
+
+    ///Adds another name for an exercise.  An alias that already exists is moved to this exercise,
+    ///so a wrong match can be corrected by adding the alias again.
+    /// # Arguments
+    /// * `name`: The name of the exercise
+    /// * `alias`: The other name of the exercise
+    /// # Returns
+    /// * [`Ok`] if the alias was saved
+    /// * [`TrainerError::ExerciseNotFound`] if the exercise was not found
+    /// * [`TrainerError::SaveFailed`] if there was a problem saving the alias
+    #[instrument(skip(self), fields(name = name, alias = alias))]
+    async fn add_alias(&self, name: String, alias: String) -> ExerciseResult<()> {
+        let exercise = self.get_by_name(name).await?;
+        let alias = ExerciseAlias {
+            alias,
+            // We can unwrap here because the repository always assigns an id
+            exercise_id: exercise.id.unwrap(),
+        };
+        match self.repo.create_alias(&alias).await {
+            Ok(_) => Ok(()),
+            Err(RepositoryError::ItemNotFoundError) => Err(ExerciseError::ExerciseNotFoundError),
+            Err(err) => {
+                error!("{}", err.to_string());
+                Err(ExerciseError::SaveFailed)
+            }
+        }
+    }
+
+    ///Retrieves the aliases of every exercise
+    #[instrument(skip(self))]
+    async fn aliases(&self) -> ExerciseResult<Vec<ExerciseAlias>> {
+        match self.repo.list_aliases().await {
+            Ok(aliases) => Ok(aliases),
+            Err(err) => {
+                error!("{}", err.to_string());
+                Err(ExerciseError::LookupError)
+            }
+        }
+    }
 }
 #[cfg(test)]
 mod tests {
@@ -197,19 +314,23 @@ mod tests {
 
     fn deadlift(id: Option<i64>) -> Exercise {
         Exercise {
-            id: id,
+            id,
             name: "Deadlift".to_string(),
             description: Some("A lift made from a standing position, without the use of a bench or other equipment.".to_string()),
             exercise_type: ExerciseType::Barbell,
+            bar_weight: None,
+            load_increment: None,
         }
     }
 
     fn benchpress(id: Option<i64>) -> Exercise {
         Exercise{
-            id: id,
+            id,
             name: "Benchpress".to_string(),
             description: Some("A lift or exercise in which a weight is raised by extending the arms upward while lying on a bench.".to_string()),
             exercise_type: ExerciseType::Barbell,
+            bar_weight: None,
+            load_increment: None,
         }
     }
 
@@ -231,6 +352,8 @@ mod tests {
                     name: "Deadlift".to_string(),
                     description: None,
                     exercise_type: ExerciseType::Barbell,
+                    bar_weight: None,
+                    load_increment: None,
                 })
             });
 
@@ -551,6 +674,134 @@ mod tests {
         assert!(matches!(result.err().unwrap(), ExerciseError::UnknownError))
     }
 
+    #[test(tokio::test)]
+    async fn autocomplete_ok() {
+        let mut repo = MockExerciseRepository::new();
+        repo.expect_query_by_prefix()
+            .with(eq("de".to_string()), eq(10))
+            .times(1)
+            .returning(|_prefix, _limit| Ok(vec![deadlift(Some(1))]));
+
+        let mgr = ExerciseManager::new(&repo).unwrap();
+        let result = mgr.autocomplete("de".to_string(), 10).await.unwrap();
+        assert_eq!(result, vec![deadlift(Some(1))]);
+    }
+
+    #[test(tokio::test)]
+    async fn autocomplete_failed() {
+        let mut repo = MockExerciseRepository::new();
+        repo.expect_query_by_prefix()
+            .returning(|_prefix, _limit| Err(RepositoryError::QueryError("db error".to_string())));
+
+        let mgr = ExerciseManager::new(&repo).unwrap();
+        let result = mgr.autocomplete("de".to_string(), 10).await;
+        assert!(matches!(result.err().unwrap(), ExerciseError::LookupError))
+    }
+
+    #[test(tokio::test)]
+    async fn restore_ok() {
+        let mut repo = MockExerciseRepository::new();
+        repo.expect_list_deleted()
+            .returning(|| Ok(vec![benchpress(Some(2)), deadlift(Some(1))]));
+        repo.expect_restore()
+            .with(eq(1))
+            .times(1)
+            .returning(|_id| Ok(()));
+
+        let mgr = ExerciseManager::new(&repo).unwrap();
+        let result = mgr.restore("DEADLIFT".to_string()).await;
+        assert!(result.is_ok())
+    }
+
+    #[test(tokio::test)]
+    async fn restore_not_found() {
+        let mut repo = MockExerciseRepository::new();
+        repo.expect_list_deleted()
+            .returning(|| Ok(vec![benchpress(Some(2))]));
+        repo.expect_restore().never();
+
+        let mgr = ExerciseManager::new(&repo).unwrap();
+        let result = mgr.restore("Deadlift".to_string()).await;
+        assert!(matches!(result.err().unwrap(), ExerciseNotFoundError))
+    }
+
+    #[test(tokio::test)]
+    async fn restore_failed() {
+        let mut repo = MockExerciseRepository::new();
+        repo.expect_list_deleted()
+            .returning(|| Ok(vec![deadlift(Some(1))]));
+        repo.expect_restore()
+            .returning(|_id| Err(RepositoryError::PersistenceError("db error".to_string())));
+
+        let mgr = ExerciseManager::new(&repo).unwrap();
+        let result = mgr.restore("Deadlift".to_string()).await;
+        assert!(matches!(
+            result.err().unwrap(),
+            ExerciseError::RestoreFailed
+        ))
+    }
+
+    #[test(tokio::test)]
+    async fn add_alias_ok() {
+        let mut repo = MockExerciseRepository::new();
+        repo.expect_query_by_name()
+            .with(eq("Deadlift".to_string()))
+            .returning(|_string| Ok(deadlift(Some(3))));
+        repo.expect_create_alias()
+            .with(eq(ExerciseAlias {
+                alias: "Conventional Deadlift".to_string(),
+                exercise_id: 3,
+            }))
+            .times(1)
+            .returning(|_alias| Ok(()));
+
+        let mgr = ExerciseManager::new(&repo).unwrap();
+        let result = mgr
+            .add_alias("Deadlift".to_string(), "Conventional Deadlift".to_string())
+            .await;
+        assert!(result.is_ok())
+    }
+
+    #[test(tokio::test)]
+    async fn add_alias_not_found() {
+        let mut repo = MockExerciseRepository::new();
+        repo.expect_query_by_name()
+            .returning(|_string| Err(ItemNotFoundError));
+        repo.expect_create_alias().never();
+
+        let mgr = ExerciseManager::new(&repo).unwrap();
+        let result = mgr
+            .add_alias("Deadlift".to_string(), "DL".to_string())
+            .await;
+        assert!(matches!(result.err().unwrap(), ExerciseNotFoundError))
+    }
+
+    #[test(tokio::test)]
+    async fn add_alias_failed() {
+        let mut repo = MockExerciseRepository::new();
+        repo.expect_query_by_name()
+            .returning(|_string| Ok(deadlift(Some(3))));
+        repo.expect_create_alias()
+            .returning(|_alias| Err(RepositoryError::PersistenceError("db error".to_string())));
+
+        let mgr = ExerciseManager::new(&repo).unwrap();
+        let result = mgr
+            .add_alias("Deadlift".to_string(), "DL".to_string())
+            .await;
+        assert!(matches!(result.err().unwrap(), ExerciseError::SaveFailed))
+    }
+
+    #[test(tokio::test)]
+    async fn aliases_failed() {
+        let mut repo = MockExerciseRepository::new();
+        repo.expect_list_aliases()
+            .returning(|| Err(RepositoryError::QueryError("db error".to_string())));
+
+        let mgr = ExerciseManager::new(&repo).unwrap();
+        let result = mgr.aliases().await;
+        assert!(matches!(result.err().unwrap(), ExerciseError::LookupError))
+    }
+
     #[test]
     fn from_string_to_exercise_type_ok() {
         let bbs = vec![
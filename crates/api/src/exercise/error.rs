@@ -1,14 +1,65 @@
+use crate::TrainerErrorCode;
+
 pub type ExerciseResult<T, E = ExerciseError> = Result<T, E>;
-#[derive(Debug, Clone)]
+#[derive(thiserror::Error, Debug, Clone)]
 #[non_exhaustive]
 pub enum ExerciseError {
+    #[error("ExerciseNotFoundError")]
     ExerciseNotFoundError,
+
+    #[error("LookupError")]
     LookupError,
+
+    #[error("SaveFailed")]
     SaveFailed,
+
+    #[error("DeleteFailed")]
     DeleteFailed,
+
+    #[error("RestoreFailed")]
+    RestoreFailed,
+
+    #[error("DuplicateNameError")]
+    DuplicateNameError,
+
+    #[error("UnknownError")]
     UnknownError,
 }
 
+impl ExerciseError {
+    pub fn code(&self) -> TrainerErrorCode {
+        match self {
+            ExerciseError::ExerciseNotFoundError => TrainerErrorCode::ExerciseNotFound,
+            ExerciseError::LookupError => TrainerErrorCode::LookupFailed,
+            ExerciseError::SaveFailed => TrainerErrorCode::SaveFailed,
+            ExerciseError::DeleteFailed => TrainerErrorCode::DeleteFailed,
+            ExerciseError::RestoreFailed => TrainerErrorCode::RestoreFailed,
+            ExerciseError::DuplicateNameError => TrainerErrorCode::DuplicateName,
+            ExerciseError::UnknownError => TrainerErrorCode::Unknown,
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+#[error("unsupported exercise type: {0}")]
+pub struct ParseExerciseTypeError(pub String);
+
+impl ParseExerciseTypeError {
+    pub fn code(&self) -> TrainerErrorCode {
+        TrainerErrorCode::InvalidExerciseType
+    }
+}
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+#[error("invalid weight, expected e.g. 20kg or 45lb: {0}")]
+pub struct ParseWeightError(pub String);
+
+impl ParseWeightError {
+    pub fn code(&self) -> TrainerErrorCode {
+        TrainerErrorCode::InvalidWeight
+    }
+}
+
 pub type RepositoryResult<T, E = RepositoryError> = Result<T, E>;
 
 #[derive(thiserror::Error, Debug, Clone)]
@@ -32,6 +83,30 @@ pub enum RepositoryError {
     #[error("DuplicateIdError")]
     DuplicateIdError,
 
+    /// Another exercise, possibly a deleted one, already has the name
+    #[error("DuplicateNameError: {0}")]
+    DuplicateNameError(String),
+
+    #[error("SchemaMismatchError: the database is at schema version {database} but this build only supports up to {supported}, upgrade trainer to use it")]
+    SchemaMismatchError { database: i64, supported: i64 },
+
     #[error("Unknown: {0}")]
     UnknownError(String),
 }
+
+impl RepositoryError {
+    pub fn code(&self) -> TrainerErrorCode {
+        match self {
+            RepositoryError::PersistenceError(_) => TrainerErrorCode::PersistenceFailed,
+            RepositoryError::ConnectionError(_) => TrainerErrorCode::ConnectionFailed,
+            RepositoryError::QueryError(_) => TrainerErrorCode::QueryFailed,
+            RepositoryError::DeleteError(_) => TrainerErrorCode::DeleteFailed,
+            // Every repository reports a missing item this way, so it does not say which kind
+            RepositoryError::ItemNotFoundError => TrainerErrorCode::NotFound,
+            RepositoryError::DuplicateIdError => TrainerErrorCode::DuplicateId,
+            RepositoryError::DuplicateNameError(_) => TrainerErrorCode::DuplicateName,
+            RepositoryError::SchemaMismatchError { .. } => TrainerErrorCode::SchemaMismatch,
+            RepositoryError::UnknownError(_) => TrainerErrorCode::Unknown,
+        }
+    }
+}
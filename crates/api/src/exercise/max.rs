@@ -0,0 +1,91 @@
+use crate::Weight;
+
+/// A formula estimating the heaviest single rep (1RM) from a set of several.  They agree closely
+/// up to about 10 reps and drift apart after that.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum OneRepMaxFormula {
+    /// `weight × (1 + reps / 30)`
+    #[default]
+    Epley,
+
+    /// `weight × 36 / (37 - reps)`
+    Brzycki,
+
+    /// `weight × reps ^ 0.1`
+    Lombardi,
+}
+
+impl OneRepMaxFormula {
+    /// The estimated 1RM of lifting `weight` for `reps`, in the unit of `weight`.  A single is its
+    /// own 1RM.  `None` for 0 reps, and for 37 or more with Brzycki where the formula breaks down.
+    pub fn estimate(&self, weight: Weight, reps: u32) -> Option<Weight> {
+        if reps == 0 {
+            return None;
+        }
+        if reps == 1 {
+            return Some(weight);
+        }
+        let r = reps as f64;
+        let factor = match self {
+            OneRepMaxFormula::Epley => 1.0 + r / 30.0,
+            OneRepMaxFormula::Brzycki if reps < 37 => 36.0 / (37.0 - r),
+            OneRepMaxFormula::Brzycki => return None,
+            OneRepMaxFormula::Lombardi => r.powf(0.1),
+        };
+        Some(Weight {
+            value: weight.value * factor,
+            unit: weight.unit,
+        })
+    }
+}
+
+impl std::fmt::Display for OneRepMaxFormula {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            OneRepMaxFormula::Epley => "Epley",
+            OneRepMaxFormula::Brzycki => "Brzycki",
+            OneRepMaxFormula::Lombardi => "Lombardi",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::WeightUnit;
+    use OneRepMaxFormula::{Brzycki, Epley, Lombardi};
+
+    fn estimate(formula: OneRepMaxFormula, weight: f64, reps: u32) -> Option<f64> {
+        formula
+            .estimate(Weight::kg(weight), reps)
+            .map(|w| (w.value * 10.0).round() / 10.0)
+    }
+
+    #[test]
+    fn estimate_ok() {
+        assert_eq!(estimate(Epley, 180.0, 5), Some(210.0));
+        assert_eq!(estimate(Brzycki, 180.0, 5), Some(202.5));
+        assert_eq!(estimate(Lombardi, 180.0, 5), Some(211.4));
+        assert_eq!(
+            Epley.estimate(Weight::lb(225.0), 10).unwrap().unit,
+            WeightUnit::Lb
+        );
+    }
+
+    #[test]
+    fn estimate_single() {
+        for formula in [Epley, Brzycki, Lombardi] {
+            assert_eq!(estimate(formula, 200.0, 1), Some(200.0));
+        }
+    }
+
+    #[test]
+    fn estimate_invalid_reps() {
+        assert_eq!(estimate(Epley, 100.0, 0), None);
+        assert_eq!(estimate(Brzycki, 100.0, 37), None);
+        assert!(estimate(Brzycki, 100.0, 36).is_some());
+    }
+}
@@ -1,8 +1,15 @@
+#[cfg(feature = "manager")]
 pub mod api;
 mod error;
+mod max;
 mod model;
+#[cfg(feature = "manager")]
 pub mod repository;
+mod weight;
 
 pub use self::error::*;
+#[cfg(feature = "manager")]
 pub use crate::api::*;
+pub use crate::exercise::max::*;
 pub use crate::exercise::model::*;
+pub use crate::exercise::weight::*;
@@ -1,4 +1,12 @@
+use crate::{ParseExerciseTypeError, Weight};
+#[cfg(feature = "wasm")]
+use tsify::Tsify;
+
 #[derive(Clone, Debug, PartialEq, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(Tsify), tsify(missing_as_null))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
 #[non_exhaustive]
 pub enum ExerciseType {
     Barbell,
@@ -7,12 +15,52 @@ pub enum ExerciseType {
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(Tsify), tsify(missing_as_null))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[allow(dead_code)] //this is temporary as code base evolves
 pub struct Exercise {
     pub id: Option<i64>,
     pub name: String,
     pub description: Option<String>,
     pub exercise_type: ExerciseType,
+
+    /// Weight of the empty bar, which no load can go below
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "wasm", tsify(type = "string | null"))]
+    pub bar_weight: Option<Weight>,
+
+    /// Loads are rounded to a multiple of this, e.g. 2.5kg or 5lb, so they can be made up with
+    /// the plates available
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "wasm", tsify(type = "string | null"))]
+    pub load_increment: Option<Weight>,
+}
+
+impl Exercise {
+    /// The load closest to `target` that can actually be put together for this exercise.  When a
+    /// load increment is set the result is in its unit, as that is the unit of the plates.
+    pub fn loadable(&self, target: Weight) -> Weight {
+        let load = match self.load_increment {
+            Some(increment) => target.round_to(increment),
+            None => target,
+        };
+        match self.bar_weight {
+            Some(bar) if bar.to_unit(load.unit).value > load.value => bar.to_unit(load.unit),
+            _ => load,
+        }
+    }
+}
+
+/// Another name an exercise is known by, e.g. the name another app uses for it.  Aliases are
+/// remembered so importers resolve the name the same way every time.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(Tsify), tsify(missing_as_null))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ExerciseAlias {
+    pub alias: String,
+    pub exercise_id: i64,
 }
 
 impl From<ExerciseType> for i64 {
@@ -38,23 +86,57 @@ impl From<i64> for ExerciseType {
 
 impl From<String> for ExerciseType {
     fn from(value: String) -> Self {
-        let lower = value.to_lowercase();
+        match value.parse() {
+            Ok(et) => et,
+            Err(_) => panic!("unsupported value"),
+        }
+    }
+}
+
+impl std::str::FromStr for ExerciseType {
+    type Err = ParseExerciseTypeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.trim().to_lowercase();
         match lower.as_str() {
-            "barbell" => ExerciseType::Barbell,
-            "bb" => ExerciseType::Barbell,
-            "kettlebell" => ExerciseType::KettleBell,
-            "kb" => ExerciseType::KettleBell,
-            "bw" => ExerciseType::BodyWeight,
-            "bodyweight" => ExerciseType::BodyWeight,
-            _ => panic!("unsupported value"),
+            "barbell" => Ok(ExerciseType::Barbell),
+            "bb" => Ok(ExerciseType::Barbell),
+            "kettlebell" => Ok(ExerciseType::KettleBell),
+            "kb" => Ok(ExerciseType::KettleBell),
+            "bw" => Ok(ExerciseType::BodyWeight),
+            "bodyweight" => Ok(ExerciseType::BodyWeight),
+            _ => Err(ParseExerciseTypeError(s.to_string())),
         }
     }
 }
 
+impl std::fmt::Display for ExerciseType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            ExerciseType::Barbell => "Barbell",
+            ExerciseType::KettleBell => "KettleBell",
+            ExerciseType::BodyWeight => "BodyWeight",
+        };
+        write!(f, "{}", label)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn exercise_type_display_round_trips() {
+        for et in [
+            ExerciseType::Barbell,
+            ExerciseType::KettleBell,
+            ExerciseType::BodyWeight,
+        ] {
+            let parsed: ExerciseType = et.to_string().into();
+            assert_eq!(parsed, et)
+        }
+    }
+
     #[test]
     fn from_string_to_exercise_type_ok() {
         let bbs = vec![
@@ -99,6 +181,47 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg(feature = "serde")]
+    fn exercise_serializes_with_stable_field_names() {
+        let exercise = Exercise {
+            id: Some(1),
+            name: "Swing".to_string(),
+            description: None,
+            exercise_type: ExerciseType::KettleBell,
+            bar_weight: None,
+            load_increment: Some(Weight::kg(4.0)),
+        };
+        let json = serde_json::to_string(&exercise).unwrap();
+        assert_eq!(
+            json,
+            r#"{"id":1,"name":"Swing","description":null,"exercise_type":"kettlebell","bar_weight":null,"load_increment":"4kg"}"#
+        );
+
+        let parsed: Exercise = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, exercise);
+
+        // Documents written before the loading rules existed still parse
+        let parsed: Exercise = serde_json::from_str(
+            r#"{"id":1,"name":"Swing","description":null,"exercise_type":"kettlebell"}"#,
+        )
+        .unwrap();
+        assert_eq!(parsed.load_increment, None);
+    }
+
+    #[test]
+    fn parse_exercise_type() {
+        assert_eq!("KB".parse::<ExerciseType>(), Ok(ExerciseType::KettleBell));
+        assert_eq!(
+            " barbell ".parse::<ExerciseType>(),
+            Ok(ExerciseType::Barbell)
+        );
+        assert_eq!(
+            "band".parse::<ExerciseType>(),
+            Err(ParseExerciseTypeError("band".to_string()))
+        );
+    }
+
     #[test]
     #[should_panic]
     fn from_string_to_exercise_type_fail() {
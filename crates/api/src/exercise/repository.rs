@@ -3,8 +3,8 @@ use async_trait::async_trait;
 #[cfg(test)]
 use mockall::automock;
 
-use crate::Exercise;
 use crate::RepositoryResult;
+use crate::{Exercise, ExerciseAlias};
 
 #[cfg_attr(test, automock)]
 #[async_trait]
@@ -25,6 +25,24 @@ pub trait ExerciseRepository {
 
     async fn list(&self) -> RepositoryResult<Vec<Exercise>>;
 
+    // Retrieves at most `limit` exercises whose name starts with `prefix` (case-insensitive),
+    // ordered by name.  Used for autocompletion, so it must stay cheap enough to call per keystroke
+    async fn query_by_prefix(&self, prefix: String, limit: u32) -> RepositoryResult<Vec<Exercise>>;
+
     /// Deletes an exercise from the repository
     async fn delete(&self, id: i64) -> RepositoryResult<()>;
+
+    /// Lists the exercises that have been deleted, which can still be restored
+    async fn list_deleted(&self) -> RepositoryResult<Vec<Exercise>>;
+
+    /// Restores a deleted exercise.
+    /// Will return an ItemNotFoundError if there is no deleted exercise with the id
+    async fn restore(&self, id: i64) -> RepositoryResult<()>;
+
+    /// Stores an alias, replacing the exercise an existing alias of the same name points to.
+    /// Will return an ItemNotFoundError if the exercise does not exist
+    async fn create_alias(&self, alias: &ExerciseAlias) -> RepositoryResult<()>;
+
+    /// Lists the aliases of every exercise that hasn't been deleted
+    async fn list_aliases(&self) -> RepositoryResult<Vec<ExerciseAlias>>;
 }
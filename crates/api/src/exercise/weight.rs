@@ -0,0 +1,198 @@
+use crate::ParseWeightError;
+
+pub const KG_PER_LB: f64 = 0.453_592_37;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum WeightUnit {
+    #[default]
+    Kg,
+    Lb,
+}
+
+impl std::fmt::Display for WeightUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WeightUnit::Kg => write!(f, "kg"),
+            WeightUnit::Lb => write!(f, "lb"),
+        }
+    }
+}
+
+/// A weight together with the unit it was given in.  The unit is kept rather than normalizing
+/// to kilograms because plates come in one unit or the other, e.g. a 5 lb increment is not the
+/// same thing as a 2.27 kg one.  Serialized as its display form, e.g. `"20kg"`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(into = "String", try_from = "String"))]
+pub struct Weight {
+    pub value: f64,
+    pub unit: WeightUnit,
+}
+
+impl Weight {
+    pub fn kg(value: f64) -> Self {
+        Self {
+            value,
+            unit: WeightUnit::Kg,
+        }
+    }
+
+    pub fn lb(value: f64) -> Self {
+        Self {
+            value,
+            unit: WeightUnit::Lb,
+        }
+    }
+
+    pub fn to_unit(self, unit: WeightUnit) -> Self {
+        let value = match (self.unit, unit) {
+            (WeightUnit::Kg, WeightUnit::Lb) => self.value / KG_PER_LB,
+            (WeightUnit::Lb, WeightUnit::Kg) => self.value * KG_PER_LB,
+            _ => self.value,
+        };
+        Self { value, unit }
+    }
+
+    /// Rounds to the nearest multiple of `increment`, in the unit of the increment.
+    pub fn round_to(self, increment: Weight) -> Self {
+        let weight = self.to_unit(increment.unit);
+        if increment.value <= 0.0 {
+            return weight;
+        }
+        let steps = (weight.value / increment.value).round();
+        Self {
+            // Trim the float noise of e.g. 0.1 increments
+            value: (steps * increment.value * 1000.0).round() / 1000.0,
+            unit: increment.unit,
+        }
+    }
+}
+
+impl std::fmt::Display for Weight {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.value, self.unit)
+    }
+}
+
+impl std::str::FromStr for Weight {
+    type Err = ParseWeightError;
+
+    /// Parses a weight such as `20kg`, `2.5 kg` or `45lbs`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.trim().to_lowercase();
+        let split = lower
+            .find(|c: char| c.is_ascii_alphabetic())
+            .ok_or_else(|| ParseWeightError(s.to_string()))?;
+        let (value, unit) = lower.split_at(split);
+        let unit = match unit.trim() {
+            "kg" | "kgs" => WeightUnit::Kg,
+            "lb" | "lbs" => WeightUnit::Lb,
+            _ => return Err(ParseWeightError(s.to_string())),
+        };
+        match value.trim().parse::<f64>() {
+            Ok(value) if value.is_finite() && value >= 0.0 => Ok(Self { value, unit }),
+            _ => Err(ParseWeightError(s.to_string())),
+        }
+    }
+}
+
+impl From<Weight> for String {
+    fn from(value: Weight) -> Self {
+        value.to_string()
+    }
+}
+
+impl TryFrom<String> for Weight {
+    type Error = ParseWeightError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for Weight {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "Weight".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "A weight in kilograms or pounds, e.g. \"20kg\" or \"45lb\"",
+            "type": "string",
+            "pattern": "^\\s*[0-9]+(\\.[0-9]+)?\\s*([kK][gG][sS]?|[lL][bB][sS]?)\\s*$"
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_ok() {
+        assert_eq!("20kg".parse::<Weight>(), Ok(Weight::kg(20.0)));
+        assert_eq!(" 2.5 KG ".parse::<Weight>(), Ok(Weight::kg(2.5)));
+        assert_eq!("45lbs".parse::<Weight>(), Ok(Weight::lb(45.0)));
+        assert_eq!("5 lb".parse::<Weight>(), Ok(Weight::lb(5.0)));
+    }
+
+    #[test]
+    fn parse_fail() {
+        for value in ["20", "kg", "20 stone", "-5kg", "twentykg", ""] {
+            assert_eq!(
+                value.parse::<Weight>(),
+                Err(ParseWeightError(value.to_string())),
+                "{}",
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn display_round_trips() {
+        for weight in [Weight::kg(20.0), Weight::kg(1.25), Weight::lb(45.0)] {
+            assert_eq!(weight.to_string().parse::<Weight>(), Ok(weight));
+        }
+        assert_eq!(Weight::kg(2.5).to_string(), "2.5kg");
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_as_string() {
+        assert_eq!(
+            serde_json::to_string(&Weight::lb(45.0)).unwrap(),
+            "\"45lb\""
+        );
+        assert_eq!(
+            serde_json::from_str::<Weight>("\"2.5kg\"").unwrap(),
+            Weight::kg(2.5)
+        );
+        assert!(serde_json::from_str::<Weight>("20").is_err());
+    }
+
+    #[test]
+    fn convert() {
+        let lb = Weight::kg(100.0).to_unit(WeightUnit::Lb);
+        assert!((lb.value - 220.462).abs() < 0.001);
+        assert_eq!(Weight::lb(45.0).to_unit(WeightUnit::Lb), Weight::lb(45.0));
+    }
+
+    #[test]
+    fn round_to() {
+        assert_eq!(
+            Weight::kg(101.2).round_to(Weight::kg(2.5)),
+            Weight::kg(100.0)
+        );
+        assert_eq!(
+            Weight::kg(101.3).round_to(Weight::kg(2.5)),
+            Weight::kg(102.5)
+        );
+        assert_eq!(Weight::kg(70.3).round_to(Weight::kg(0.1)), Weight::kg(70.3));
+        assert_eq!(
+            Weight::kg(100.0).round_to(Weight::lb(5.0)),
+            Weight::lb(220.0)
+        );
+        assert_eq!(Weight::kg(52.0).round_to(Weight::kg(0.0)), Weight::kg(52.0));
+    }
+}
@@ -1,4 +1,13 @@
+mod error_code;
 pub mod exercise;
+#[cfg(feature = "schema")]
+pub mod schema;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod workout;
 
+pub use crate::error_code::*;
 pub use crate::exercise::*;
+#[cfg(feature = "manager")]
 pub use crate::repository::*;
+pub use crate::workout::*;
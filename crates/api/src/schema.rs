@@ -0,0 +1,84 @@
+//! JSON Schema documents for the models, enabled with the `schema` feature.  The schemas follow the
+//! serde representation, so they validate exactly what the models serialize to and accept.
+use crate::{Exercise, ExerciseAlias, ExerciseType, PerformedExercise, Set, WorkoutSession};
+use schemars::schema_for;
+use serde_json::Value;
+
+/// Names of the models with a schema, as accepted by [`schema`].
+pub const SCHEMA_NAMES: [&str; 6] = [
+    "exercise",
+    "exercise-type",
+    "exercise-alias",
+    "workout-session",
+    "performed-exercise",
+    "set",
+];
+
+/// The JSON Schema of the model called `name`, or `None` for an unknown name.
+pub fn schema(name: &str) -> Option<Value> {
+    let schema = match name {
+        "exercise" => schema_for!(Exercise),
+        "exercise-type" => schema_for!(ExerciseType),
+        "exercise-alias" => schema_for!(ExerciseAlias),
+        "workout-session" => schema_for!(WorkoutSession),
+        "performed-exercise" => schema_for!(PerformedExercise),
+        "set" => schema_for!(Set),
+        _ => return None,
+    };
+    Some(schema.to_value())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn schema_ok() {
+        let exercise = schema("exercise").unwrap();
+        assert_eq!(exercise["title"], "Exercise");
+        assert_eq!(
+            exercise["required"],
+            serde_json::json!(["name", "exercise_type"])
+        );
+
+        let exercise_type = schema("exercise-type").unwrap();
+        assert_eq!(
+            exercise_type["enum"],
+            serde_json::json!(["barbell", "kettlebell", "bodyweight"])
+        );
+
+        let session = schema("workout-session").unwrap();
+        assert_eq!(session["properties"]["date"]["format"], "date");
+        assert_eq!(session["required"], serde_json::json!(["date"]));
+
+        let set = schema("set").unwrap();
+        assert_eq!(
+            set["properties"]["weight"]["anyOf"][0]["$ref"],
+            "#/$defs/Weight"
+        );
+
+        assert_eq!(schema("workout"), None);
+    }
+
+    /// Keeps the checked in schemas in lockstep with the models.  Run with `UPDATE_SCHEMAS=1` to
+    /// regenerate them after changing a model.
+    #[test]
+    fn schemas_up_to_date() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("schema");
+        for name in SCHEMA_NAMES {
+            let path = dir.join(format!("{}.json", name));
+            let contents = serde_json::to_string_pretty(&schema(name).unwrap()).unwrap() + "\n";
+            if std::env::var_os("UPDATE_SCHEMAS").is_some() {
+                std::fs::create_dir_all(&dir).unwrap();
+                std::fs::write(&path, &contents).unwrap();
+            }
+            assert_eq!(
+                std::fs::read_to_string(&path).unwrap(),
+                contents,
+                "{} is out of date, run the tests with UPDATE_SCHEMAS=1",
+                path.display()
+            );
+        }
+    }
+}
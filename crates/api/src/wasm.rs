@@ -0,0 +1,75 @@
+//! wasm-bindgen exports for browser frontends, enabled with the `wasm` feature.  The models derive
+//! `Tsify` so their TypeScript declarations are emitted into the generated `.d.ts`, and values
+//! cross the boundary as [`Ts`] so that invalid input from JavaScript is an ordinary error.
+use crate::{Exercise, ExerciseAlias, ExerciseType, PerformedExercise, Set, WorkoutSession};
+use tsify::{Ts, Tsify};
+use wasm_bindgen::prelude::*;
+
+/// TypeScript declarations of every model exported to JavaScript, as written to
+/// `bindings/trainer.d.ts` for frontends that use the REST models without the wasm module.
+pub fn typescript_declarations() -> String {
+    let mut out = String::from("// Generated from the api crate models, do not edit.\n");
+    for decl in [
+        ExerciseType::DECL,
+        Exercise::DECL,
+        ExerciseAlias::DECL,
+        WorkoutSession::DECL,
+        PerformedExercise::DECL,
+        Set::DECL,
+    ] {
+        out.push('\n');
+        out.push_str(decl);
+        out.push('\n');
+    }
+    out
+}
+
+/// Parses an exercise type the way the CLI and importers do, accepting aliases such as `kb`.
+#[wasm_bindgen(js_name = parseExerciseType)]
+pub fn parse_exercise_type(value: &str) -> Result<Ts<ExerciseType>, JsError> {
+    let exercise_type: ExerciseType = value.parse()?;
+    Ok(exercise_type.into_ts()?)
+}
+
+/// Checks that a value from JavaScript is a well-formed [`Exercise`], returning it normalized.
+#[wasm_bindgen(js_name = validateExercise)]
+pub fn validate_exercise(exercise: Ts<Exercise>) -> Result<Ts<Exercise>, JsError> {
+    let exercise: Exercise = exercise.to_rust()?;
+    Ok(exercise.into_ts()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn declarations() {
+        assert_eq!(
+            ExerciseType::DECL,
+            r#"export type ExerciseType = "barbell" | "kettlebell" | "bodyweight";"#
+        );
+        assert!(Exercise::DECL.starts_with("export interface Exercise {"));
+        assert!(Exercise::DECL.contains("exercise_type: ExerciseType;"));
+        assert!(WorkoutSession::DECL.contains("date: string;"));
+        assert!(WorkoutSession::DECL.contains("exercises?: PerformedExercise[];"));
+        assert!(Set::DECL.contains("weight?: string | null;"));
+    }
+
+    /// Keeps the checked in declarations in lockstep with the models.  Run with
+    /// `UPDATE_BINDINGS=1` to regenerate them after changing a model.
+    #[test]
+    fn bindings_up_to_date() {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("bindings/trainer.d.ts");
+        let declarations = typescript_declarations();
+        if std::env::var_os("UPDATE_BINDINGS").is_some() {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, &declarations).unwrap();
+        }
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            declarations,
+            "{} is out of date, run the tests with UPDATE_BINDINGS=1",
+            path.display()
+        );
+    }
+}
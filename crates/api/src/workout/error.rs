@@ -0,0 +1,41 @@
+use crate::TrainerErrorCode;
+
+pub type WorkoutResult<T, E = WorkoutError> = Result<T, E>;
+#[derive(thiserror::Error, Debug, Clone)]
+#[non_exhaustive]
+pub enum WorkoutError {
+    #[error("SessionNotFoundError")]
+    SessionNotFoundError,
+
+    #[error("SetNotFoundError")]
+    SetNotFoundError,
+
+    #[error("InvalidRpeError")]
+    InvalidRpeError,
+
+    #[error("LookupError")]
+    LookupError,
+
+    #[error("SaveFailed")]
+    SaveFailed,
+
+    #[error("DeleteFailed")]
+    DeleteFailed,
+
+    #[error("UnknownError")]
+    UnknownError,
+}
+
+impl WorkoutError {
+    pub fn code(&self) -> TrainerErrorCode {
+        match self {
+            WorkoutError::SessionNotFoundError => TrainerErrorCode::SessionNotFound,
+            WorkoutError::SetNotFoundError => TrainerErrorCode::SetNotFound,
+            WorkoutError::InvalidRpeError => TrainerErrorCode::InvalidRpe,
+            WorkoutError::LookupError => TrainerErrorCode::LookupFailed,
+            WorkoutError::SaveFailed => TrainerErrorCode::SaveFailed,
+            WorkoutError::DeleteFailed => TrainerErrorCode::DeleteFailed,
+            WorkoutError::UnknownError => TrainerErrorCode::Unknown,
+        }
+    }
+}
@@ -0,0 +1,349 @@
+use crate::workout::repository::WorkoutSessionRepository;
+use crate::{RepositoryError, Set, WorkoutError, WorkoutResult, WorkoutSession};
+use async_trait::async_trait;
+use tracing::{debug, error, instrument};
+
+#[async_trait]
+pub trait WorkoutManagement {
+    // Will create or update a session
+    async fn save_session(&self, session: &mut WorkoutSession) -> WorkoutResult<()>;
+
+    async fn get_session(&self, id: i64) -> WorkoutResult<WorkoutSession>;
+
+    // Records a completed set during a workout, numbering it after the sets of its exercise
+    async fn record_set(&self, set: &mut Set) -> WorkoutResult<()>;
+
+    // Corrects the weight, reps or RPE of a recorded set
+    async fn update_set(&self, set: &Set) -> WorkoutResult<()>;
+
+    async fn delete_set(&self, id: i64) -> WorkoutResult<()>;
+
+    // The sets of a session in the order they were recorded
+    async fn sets(&self, session_id: i64) -> WorkoutResult<Vec<Set>>;
+}
+
+#[derive(Clone, Debug)]
+pub struct WorkoutManager<'a, T: WorkoutSessionRepository> {
+    repo: &'a T,
+}
+
+impl<'a, T: WorkoutSessionRepository> WorkoutManager<'a, T> {
+    pub fn new(repo: &'a T) -> WorkoutResult<Self> {
+        Ok(Self { repo })
+    }
+}
+
+/// Logs a repository error and converts it, reporting a missing item as `not_found` and any
+/// other problem as `failed`.
+fn convert(err: RepositoryError, not_found: WorkoutError, failed: WorkoutError) -> WorkoutError {
+    match err {
+        RepositoryError::ItemNotFoundError => {
+            debug!("{}", not_found);
+            not_found
+        }
+        err => {
+            error!("{}", err.to_string());
+            failed
+        }
+    }
+}
+
+/// Rejects an RPE outside of 1 to 10 before it reaches the repository.
+fn check_rpe(set: &Set) -> WorkoutResult<()> {
+    match set.rpe {
+        Some(rpe) if !(1.0..=10.0).contains(&rpe) => {
+            debug!("rejected RPE {}", rpe);
+            Err(WorkoutError::InvalidRpeError)
+        }
+        _ => Ok(()),
+    }
+}
+
+#[async_trait]
+impl<T: WorkoutSessionRepository + Sync + std::fmt::Debug> WorkoutManagement
+    for WorkoutManager<'_, T>
+{
+    /// Creates the session when it has no identifier yet, assigning the one the repository
+    /// generated, and replaces it otherwise
+    ///
+    /// # Returns
+    /// * [`Ok`] if the save is successful
+    /// * [`WorkoutError::SessionNotFoundError`] if the identifier of the session is not found
+    /// * [`WorkoutError::SaveFailed`] if there is a problem saving the session, e.g. when it
+    ///   leaves out an exercise that still has sets
+    #[instrument(skip(self), fields(date = %session.date))]
+    async fn save_session(&self, session: &mut WorkoutSession) -> WorkoutResult<()> {
+        match session.id {
+            None => {
+                let id =
+                    self.repo.create_session(session).await.map_err(|e| {
+                        convert(e, WorkoutError::SaveFailed, WorkoutError::SaveFailed)
+                    })?;
+                debug!("received id {} from repository", id);
+                session.id = Some(id);
+                Ok(())
+            }
+            Some(_) => self.repo.update_session(session).await.map_err(|e| {
+                convert(
+                    e,
+                    WorkoutError::SessionNotFoundError,
+                    WorkoutError::SaveFailed,
+                )
+            }),
+        }
+    }
+
+    #[instrument(skip(self))]
+    async fn get_session(&self, id: i64) -> WorkoutResult<WorkoutSession> {
+        self.repo.query_session_by_id(id).await.map_err(|e| {
+            convert(
+                e,
+                WorkoutError::SessionNotFoundError,
+                WorkoutError::LookupError,
+            )
+        })
+    }
+
+    /// Records `set`, assigning its identifier and the next set number of its exercise in the
+    /// session; the number the caller gave is ignored.  The first set of an exercise also adds
+    /// the exercise to the performed exercises of the session, in the same transaction.
+    ///
+    /// # Returns
+    /// * [`Ok`] if the set was recorded
+    /// * [`WorkoutError::InvalidRpeError`] if the RPE is not between 1 and 10
+    /// * [`WorkoutError::SessionNotFoundError`] if the session does not exist
+    /// * [`WorkoutError::SaveFailed`] if there is a problem saving the set, e.g. for an exercise
+    ///   that does not exist
+    #[instrument(skip(self), fields(session_id = set.session_id, exercise_id = set.exercise_id))]
+    async fn record_set(&self, set: &mut Set) -> WorkoutResult<()> {
+        check_rpe(set)?;
+        let recorded = self.repo.record_set(set).await.map_err(|e| {
+            convert(
+                e,
+                WorkoutError::SessionNotFoundError,
+                WorkoutError::SaveFailed,
+            )
+        })?;
+        debug!("recorded set {} as {:?}", recorded.set_number, recorded.id);
+        *set = recorded;
+        Ok(())
+    }
+
+    /// Replaces the recorded `set`
+    ///
+    /// # Returns
+    /// * [`Ok`] if the set was updated
+    /// * [`WorkoutError::InvalidRpeError`] if the RPE is not between 1 and 10
+    /// * [`WorkoutError::SetNotFoundError`] if the set does not exist
+    /// * [`WorkoutError::SaveFailed`] if there is a problem saving the set
+    #[instrument(skip(self), fields(id = set.id))]
+    async fn update_set(&self, set: &Set) -> WorkoutResult<()> {
+        check_rpe(set)?;
+        self.repo
+            .update_set(set)
+            .await
+            .map_err(|e| convert(e, WorkoutError::SetNotFoundError, WorkoutError::SaveFailed))
+    }
+
+    #[instrument(skip(self))]
+    async fn delete_set(&self, id: i64) -> WorkoutResult<()> {
+        self.repo.delete_set(id).await.map_err(|e| {
+            convert(
+                e,
+                WorkoutError::SetNotFoundError,
+                WorkoutError::DeleteFailed,
+            )
+        })
+    }
+
+    #[instrument(skip(self))]
+    async fn sets(&self, session_id: i64) -> WorkoutResult<Vec<Set>> {
+        self.repo.list_sets(session_id).await.map_err(|e| {
+            convert(
+                e,
+                WorkoutError::SessionNotFoundError,
+                WorkoutError::LookupError,
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RepositoryError::{ItemNotFoundError, PersistenceError, QueryError};
+    use crate::{MockWorkoutSessionRepository, NaiveDate, PerformedExercise, Weight};
+    use mockall::predicate::eq;
+    use std::collections::BTreeMap;
+    use test_log::test;
+
+    fn session(exercise_ids: &[i64]) -> WorkoutSession {
+        WorkoutSession {
+            id: Some(3),
+            date: NaiveDate::from_ymd_opt(2024, 5, 1).unwrap(),
+            name: None,
+            notes: None,
+            exercises: exercise_ids
+                .iter()
+                .map(|&exercise_id| PerformedExercise {
+                    exercise_id,
+                    notes: None,
+                })
+                .collect(),
+            metrics: BTreeMap::new(),
+        }
+    }
+
+    fn squat_set(id: Option<i64>, set_number: u32) -> Set {
+        Set {
+            id,
+            session_id: 3,
+            exercise_id: 1,
+            set_number,
+            weight: Some(Weight::kg(140.0)),
+            reps: 5,
+            rpe: None,
+        }
+    }
+
+    #[test(tokio::test)]
+    async fn save_session_new_ok() {
+        let mut repo = MockWorkoutSessionRepository::new();
+        repo.expect_create_session().times(1).returning(|_| Ok(3));
+        let mgr = WorkoutManager::new(&repo).unwrap();
+
+        let mut new = session(&[]);
+        new.id = None;
+        mgr.save_session(&mut new).await.unwrap();
+        assert_eq!(new.id, Some(3));
+    }
+
+    #[test(tokio::test)]
+    async fn save_session_existing_not_found() {
+        let mut repo = MockWorkoutSessionRepository::new();
+        repo.expect_update_session()
+            .times(1)
+            .returning(|_| Err(ItemNotFoundError));
+        let mgr = WorkoutManager::new(&repo).unwrap();
+
+        let result = mgr.save_session(&mut session(&[])).await;
+        assert!(matches!(
+            result.err().unwrap(),
+            WorkoutError::SessionNotFoundError
+        ));
+    }
+
+    #[test(tokio::test)]
+    async fn get_session_failed() {
+        let mut repo = MockWorkoutSessionRepository::new();
+        repo.expect_query_session_by_id()
+            .with(eq(3))
+            .returning(|_| Err(QueryError("disk I/O error".to_string())));
+        let mgr = WorkoutManager::new(&repo).unwrap();
+        assert!(matches!(
+            mgr.get_session(3).await.err().unwrap(),
+            WorkoutError::LookupError
+        ));
+    }
+
+    #[test(tokio::test)]
+    async fn record_set_ok() {
+        let mut repo = MockWorkoutSessionRepository::new();
+        repo.expect_record_set()
+            .withf(|s| s.session_id == 3 && s.exercise_id == 1)
+            .times(1)
+            .returning(|_| Ok(squat_set(Some(13), 3)));
+        repo.expect_update_session().never();
+        let mgr = WorkoutManager::new(&repo).unwrap();
+
+        let mut set = squat_set(None, 1);
+        mgr.record_set(&mut set).await.unwrap();
+        assert_eq!(set, squat_set(Some(13), 3));
+    }
+
+    #[test(tokio::test)]
+    async fn record_set_session_not_found() {
+        let mut repo = MockWorkoutSessionRepository::new();
+        repo.expect_record_set()
+            .returning(|_| Err(ItemNotFoundError));
+        let mgr = WorkoutManager::new(&repo).unwrap();
+
+        let result = mgr.record_set(&mut squat_set(None, 0)).await;
+        assert!(matches!(
+            result.err().unwrap(),
+            WorkoutError::SessionNotFoundError
+        ));
+    }
+
+    #[test(tokio::test)]
+    async fn record_set_failed() {
+        let mut repo = MockWorkoutSessionRepository::new();
+        repo.expect_record_set().returning(|_| {
+            Err(PersistenceError(
+                "FOREIGN KEY constraint failed".to_string(),
+            ))
+        });
+        repo.expect_update_session().never();
+        let mgr = WorkoutManager::new(&repo).unwrap();
+
+        let mut set = squat_set(None, 0);
+        let result = mgr.record_set(&mut set).await;
+        assert!(matches!(result.err().unwrap(), WorkoutError::SaveFailed));
+        assert_eq!(set, squat_set(None, 0));
+    }
+
+    #[test(tokio::test)]
+    async fn invalid_rpe_rejected() {
+        let mut repo = MockWorkoutSessionRepository::new();
+        repo.expect_record_set().never();
+        repo.expect_update_set().never();
+        let mgr = WorkoutManager::new(&repo).unwrap();
+
+        for rpe in [0.0, 10.5, -1.0, f64::NAN] {
+            let mut set = squat_set(None, 0);
+            set.rpe = Some(rpe);
+            assert!(matches!(
+                mgr.record_set(&mut set).await.err().unwrap(),
+                WorkoutError::InvalidRpeError
+            ));
+            set.id = Some(10);
+            assert!(matches!(
+                mgr.update_set(&set).await.err().unwrap(),
+                WorkoutError::InvalidRpeError
+            ));
+        }
+    }
+
+    #[test(tokio::test)]
+    async fn rpe_bounds_accepted() {
+        let mut repo = MockWorkoutSessionRepository::new();
+        repo.expect_update_set().times(2).returning(|_| Ok(()));
+        let mgr = WorkoutManager::new(&repo).unwrap();
+
+        for rpe in [1.0, 10.0] {
+            let mut set = squat_set(Some(10), 1);
+            set.rpe = Some(rpe);
+            mgr.update_set(&set).await.unwrap();
+        }
+    }
+
+    #[test(tokio::test)]
+    async fn update_and_delete_set_not_found() {
+        let mut repo = MockWorkoutSessionRepository::new();
+        repo.expect_update_set()
+            .returning(|_| Err(ItemNotFoundError));
+        repo.expect_delete_set()
+            .with(eq(10))
+            .returning(|_| Err(ItemNotFoundError));
+        let mgr = WorkoutManager::new(&repo).unwrap();
+
+        assert!(matches!(
+            mgr.update_set(&squat_set(Some(10), 1)).await.err().unwrap(),
+            WorkoutError::SetNotFoundError
+        ));
+        assert!(matches!(
+            mgr.delete_set(10).await.err().unwrap(),
+            WorkoutError::SetNotFoundError
+        ));
+    }
+}
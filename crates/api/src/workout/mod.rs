@@ -0,0 +1,13 @@
+mod error;
+#[cfg(feature = "manager")]
+mod manager;
+mod model;
+#[cfg(feature = "manager")]
+mod repository;
+
+pub use crate::workout::error::*;
+#[cfg(feature = "manager")]
+pub use crate::workout::manager::*;
+pub use crate::workout::model::*;
+#[cfg(feature = "manager")]
+pub use crate::workout::repository::*;
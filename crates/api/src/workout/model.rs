@@ -0,0 +1,126 @@
+use crate::Weight;
+pub use chrono::NaiveDate;
+use std::collections::BTreeMap;
+#[cfg(feature = "wasm")]
+use tsify::Tsify;
+
+/// A training session on a given day, e.g. the Monday heavy day of a program.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(Tsify), tsify(missing_as_null))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct WorkoutSession {
+    pub id: Option<i64>,
+
+    /// Day the session was trained on, serialized as e.g. `"2024-05-01"`
+    #[cfg_attr(feature = "wasm", tsify(type = "string"))]
+    pub date: NaiveDate,
+
+    pub name: Option<String>,
+    pub notes: Option<String>,
+
+    /// The exercises performed, in the order they were done
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub exercises: Vec<PerformedExercise>,
+
+    /// Numeric measurements the user tracks beyond the built in fields, keyed by a name of their
+    /// choosing, e.g. `bodyweight_kg` or `sleep_hours`
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "wasm", tsify(type = "Record<string, number>"))]
+    pub metrics: BTreeMap<String, f64>,
+}
+
+/// An exercise done during a [`WorkoutSession`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(Tsify), tsify(missing_as_null))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PerformedExercise {
+    pub exercise_id: i64,
+    pub notes: Option<String>,
+}
+
+/// A completed set of an exercise performed during a [`WorkoutSession`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(Tsify), tsify(missing_as_null))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Set {
+    pub id: Option<i64>,
+    pub session_id: i64,
+    pub exercise_id: i64,
+
+    /// Position of the set among the sets of its exercise in the session, counting from 1
+    pub set_number: u32,
+
+    /// The load lifted, `None` for a set without added weight, e.g. of pull-ups
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "wasm", tsify(type = "string | null"))]
+    pub weight: Option<Weight>,
+
+    pub reps: u32,
+
+    /// Rate of perceived exertion, from 1 to 10
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rpe: Option<f64>,
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session_serializes_with_stable_field_names() {
+        let session = WorkoutSession {
+            id: Some(3),
+            date: NaiveDate::from_ymd_opt(2024, 5, 1).unwrap(),
+            name: Some("Heavy day".to_string()),
+            notes: None,
+            exercises: vec![PerformedExercise {
+                exercise_id: 1,
+                notes: Some("belt on".to_string()),
+            }],
+            metrics: BTreeMap::from([("sleep_hours".to_string(), 7.5)]),
+        };
+        let json = serde_json::to_string(&session).unwrap();
+        assert_eq!(
+            json,
+            r#"{"id":3,"date":"2024-05-01","name":"Heavy day","notes":null,"exercises":[{"exercise_id":1,"notes":"belt on"}],"metrics":{"sleep_hours":7.5}}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<WorkoutSession>(&json).unwrap(),
+            session
+        );
+
+        // Documents written before metrics existed still parse
+        let parsed: WorkoutSession = serde_json::from_str(
+            r#"{"id":null,"date":"2024-05-01","name":null,"notes":null,"exercises":[]}"#,
+        )
+        .unwrap();
+        assert!(parsed.metrics.is_empty());
+
+        assert!(serde_json::from_str::<WorkoutSession>(
+            r#"{"id":null,"date":"2024-13-01","name":null,"notes":null}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn set_serializes_with_stable_field_names() {
+        let set = Set {
+            id: Some(7),
+            session_id: 3,
+            exercise_id: 1,
+            set_number: 2,
+            weight: Some(Weight::kg(140.0)),
+            reps: 5,
+            rpe: Some(8.5),
+        };
+        let json = serde_json::to_string(&set).unwrap();
+        assert_eq!(
+            json,
+            r#"{"id":7,"session_id":3,"exercise_id":1,"set_number":2,"weight":"140kg","reps":5,"rpe":8.5}"#
+        );
+        assert_eq!(serde_json::from_str::<Set>(&json).unwrap(), set);
+    }
+}
@@ -0,0 +1,58 @@
+use async_trait::async_trait;
+
+#[cfg(test)]
+use mockall::automock;
+
+use crate::RepositoryResult;
+use crate::{Set, WorkoutSession};
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait WorkoutSessionRepository {
+    /// Persists the session together with its performed exercises.
+    /// Will return the repository generated ID in a TrainerResult.
+    /// RepositoryError will be a PersistenceError, also when an exercise does not exist
+    async fn create_session(&self, session: &WorkoutSession) -> RepositoryResult<i64>;
+
+    /// Replaces the session, including the list of performed exercises.
+    /// Will return an ItemNotFoundError if the session does not exist.
+    /// RepositoryError will be a PersistenceError when the list leaves out an exercise that
+    /// still has sets in the session
+    async fn update_session(&self, session: &WorkoutSession) -> RepositoryResult<()>;
+
+    // Will return an ItemNotFoundError if the session does not exist
+    async fn query_session_by_id(&self, id: i64) -> RepositoryResult<WorkoutSession>;
+
+    // Lists every session, newest first
+    async fn list_sessions(&self) -> RepositoryResult<Vec<WorkoutSession>>;
+
+    // Lists the sessions recording the metric called `name`, newest first
+    async fn list_sessions_with_metric(
+        &self,
+        name: String,
+    ) -> RepositoryResult<Vec<WorkoutSession>>;
+
+    /// Deletes a session and its performed exercises.
+    /// Will return an ItemNotFoundError if the session does not exist
+    async fn delete_session(&self, id: i64) -> RepositoryResult<()>;
+
+    /// Records a completed set in one transaction: numbers it after the sets of its exercise in
+    /// the session, ignoring the number it has, and appends the exercise to the performed
+    /// exercises of the session when this is its first set.
+    /// Will return the set with its repository generated ID and number.
+    /// Will return an ItemNotFoundError if the session does not exist.
+    /// RepositoryError will be a PersistenceError, also when the exercise does not exist
+    async fn record_set(&self, set: &Set) -> RepositoryResult<Set>;
+
+    /// Replaces the set in one transaction, appending its exercise to the performed exercises
+    /// of the session when it was moved to one the session doesn't have yet.
+    /// Will return an ItemNotFoundError if the set does not exist.
+    /// RepositoryError will be a PersistenceError, also when the exercise does not exist
+    async fn update_set(&self, set: &Set) -> RepositoryResult<()>;
+
+    /// Will return an ItemNotFoundError if the set does not exist
+    async fn delete_set(&self, id: i64) -> RepositoryResult<()>;
+
+    // Lists the sets of a session in the order they were recorded
+    async fn list_sets(&self, session_id: i64) -> RepositoryResult<Vec<Set>>;
+}
@@ -0,0 +1,14 @@
+pub type I18nResult<T, E = I18nError> = Result<T, E>;
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum I18nError {
+    #[error("InvalidLocaleError: {0}")]
+    InvalidLocaleError(String),
+
+    #[error("ParseError: {0}")]
+    ParseError(String),
+
+    #[error("DuplicateMessageError: {0}")]
+    DuplicateMessageError(String),
+}
@@ -0,0 +1,164 @@
+//! Localization of the messages trainer shows to people, built on [Fluent](https://projectfluent.org).
+//! This crate ships no messages of its own; every crate registers the bundles for the strings it
+//! owns, e.g. the CLI registers its error messages for English and Spanish.
+mod error;
+
+pub use self::error::*;
+pub use fluent_bundle::{FluentArgs, FluentValue};
+
+use fluent_bundle::{FluentBundle, FluentResource};
+use std::collections::HashMap;
+use unic_langid::LanguageIdentifier;
+
+/// Locale every message is expected to exist in, used when the selected locale lacks a message.
+pub const FALLBACK_LOCALE: &str = "en";
+
+/// The registered messages of every locale and the locale messages are looked up in.
+pub struct Localizer {
+    locale: LanguageIdentifier,
+    bundles: HashMap<LanguageIdentifier, FluentBundle<FluentResource>>,
+}
+
+impl std::fmt::Debug for Localizer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Localizer")
+            .field("locale", &self.locale)
+            .field("locales", &self.bundles.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl Localizer {
+    /// A localizer for `locale`, e.g. `es` or `es-MX`, without any messages yet.
+    pub fn new(locale: &str) -> I18nResult<Self> {
+        Ok(Self {
+            locale: parse_locale(locale)?,
+            bundles: HashMap::new(),
+        })
+    }
+
+    pub fn locale(&self) -> String {
+        self.locale.to_string()
+    }
+
+    /// Adds the messages of `source`, in Fluent syntax, to `locale`.  A crate registers each of
+    /// its bundles once; a message id registered twice for the same locale is an error so two
+    /// crates can't silently replace each other's messages.
+    pub fn register(&mut self, locale: &str, source: &str) -> I18nResult<()> {
+        let locale = parse_locale(locale)?;
+        let resource = FluentResource::try_new(source.to_string()).map_err(|(_, errors)| {
+            I18nError::ParseError(
+                errors
+                    .iter()
+                    .map(|e| e.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            )
+        })?;
+
+        let bundle = self.bundles.entry(locale.clone()).or_insert_with(|| {
+            let mut bundle = FluentBundle::new(vec![locale]);
+            // Unicode isolation marks only help right-to-left text and garble terminal output
+            bundle.set_use_isolating(false);
+            bundle
+        });
+        bundle.add_resource(resource).map_err(|errors| {
+            I18nError::DuplicateMessageError(
+                errors
+                    .iter()
+                    .map(|e| e.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            )
+        })
+    }
+
+    /// The message `id` in the selected locale, then in its language without the region, then in
+    /// [`FALLBACK_LOCALE`].  An id no bundle has is returned as is, so a missing translation shows
+    /// up in the output rather than failing the command.
+    pub fn message(&self, id: &str, args: Option<&FluentArgs>) -> String {
+        let language = LanguageIdentifier::from_parts(self.locale.language, None, None, &[]);
+        let fallback: LanguageIdentifier = FALLBACK_LOCALE.parse().unwrap();
+        for locale in [&self.locale, &language, &fallback] {
+            let Some(bundle) = self.bundles.get(locale) else {
+                continue;
+            };
+            if let Some(pattern) = bundle.get_message(id).and_then(|m| m.value()) {
+                let mut errors = vec![];
+                return bundle
+                    .format_pattern(pattern, args, &mut errors)
+                    .into_owned();
+            }
+        }
+        id.to_string()
+    }
+}
+
+fn parse_locale(locale: &str) -> I18nResult<LanguageIdentifier> {
+    locale
+        .parse()
+        .map_err(|_| I18nError::InvalidLocaleError(locale.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn localizer(locale: &str) -> Localizer {
+        let mut localizer = Localizer::new(locale).unwrap();
+        localizer
+            .register("en", "hello = Hello { $name }\nbye = Goodbye\n")
+            .unwrap();
+        localizer
+            .register("es", "hello = Hola { $name }\n")
+            .unwrap();
+        localizer
+    }
+
+    fn args(name: &str) -> FluentArgs<'_> {
+        let mut args = FluentArgs::new();
+        args.set("name", name);
+        args
+    }
+
+    #[test]
+    fn message_ok() {
+        assert_eq!(
+            localizer("en").message("hello", Some(&args("Ana"))),
+            "Hello Ana"
+        );
+        assert_eq!(
+            localizer("es").message("hello", Some(&args("Ana"))),
+            "Hola Ana"
+        );
+    }
+
+    #[test]
+    fn message_fallback() {
+        let localizer = localizer("es-MX");
+        assert_eq!(localizer.message("hello", Some(&args("Ana"))), "Hola Ana");
+        assert_eq!(localizer.message("bye", None), "Goodbye");
+        assert_eq!(localizer.message("missing", None), "missing");
+        assert_eq!(
+            Localizer::new("fr").unwrap().message("hello", None),
+            "hello"
+        );
+    }
+
+    #[test]
+    fn register_fail() {
+        let mut localizer = localizer("en");
+        assert!(matches!(
+            localizer.register("en", "bye = Bye\n").err().unwrap(),
+            I18nError::DuplicateMessageError(_)
+        ));
+        assert!(matches!(
+            localizer.register("en", "= nothing\n").err().unwrap(),
+            I18nError::ParseError(_)
+        ));
+        assert!(matches!(
+            Localizer::new("not a locale").err().unwrap(),
+            I18nError::InvalidLocaleError(_)
+        ));
+    }
+}
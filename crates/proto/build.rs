@@ -0,0 +1,11 @@
+// Compiles the protobuf definitions with protox so building doesn't need protoc installed.
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let files = ["proto/trainer/v1/exercise.proto"];
+    for file in files {
+        println!("cargo:rerun-if-changed={}", file);
+    }
+
+    let descriptors = protox::compile(files, ["proto"])?;
+    prost_build::Config::new().compile_fds(descriptors)?;
+    Ok(())
+}
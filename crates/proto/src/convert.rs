@@ -0,0 +1,187 @@
+//! Conversions between the generated messages and the api models.  Decoding is fallible since a
+//! message from a newer peer may carry enum values this version doesn't know, or leave them
+//! unspecified.  Encoding an exercise is fallible too, as the api may have exercise types the
+//! messages don't have yet.
+use crate::{v1, ProtoError};
+
+impl TryFrom<api::ExerciseType> for v1::ExerciseType {
+    type Error = ProtoError;
+
+    /// Fails for an exercise type that exercise.proto doesn't have yet.
+    fn try_from(value: api::ExerciseType) -> Result<Self, Self::Error> {
+        i32::try_from(i64::from(value) + 1)
+            .ok()
+            .and_then(|number| v1::ExerciseType::try_from(number).ok())
+            .ok_or_else(|| ProtoError::UnsupportedExerciseType(value.to_string()))
+    }
+}
+
+impl TryFrom<v1::ExerciseType> for api::ExerciseType {
+    type Error = ProtoError;
+
+    fn try_from(value: v1::ExerciseType) -> Result<Self, Self::Error> {
+        match value {
+            v1::ExerciseType::Unspecified => Err(ProtoError::UnknownExerciseType(value.into())),
+            _ => Ok(api::ExerciseType::from(i64::from(value as i32) - 1)),
+        }
+    }
+}
+
+impl From<api::Weight> for v1::Weight {
+    fn from(value: api::Weight) -> Self {
+        let unit = match value.unit {
+            api::WeightUnit::Kg => v1::WeightUnit::Kg,
+            api::WeightUnit::Lb => v1::WeightUnit::Lb,
+        };
+        Self {
+            value: value.value,
+            unit: unit.into(),
+        }
+    }
+}
+
+impl TryFrom<v1::Weight> for api::Weight {
+    type Error = ProtoError;
+
+    fn try_from(value: v1::Weight) -> Result<Self, Self::Error> {
+        let unit = match v1::WeightUnit::try_from(value.unit) {
+            Ok(v1::WeightUnit::Kg) => api::WeightUnit::Kg,
+            Ok(v1::WeightUnit::Lb) => api::WeightUnit::Lb,
+            Ok(v1::WeightUnit::Unspecified) | Err(_) => {
+                return Err(ProtoError::UnknownWeightUnit(value.unit))
+            }
+        };
+        Ok(Self {
+            value: value.value,
+            unit,
+        })
+    }
+}
+
+impl TryFrom<api::Exercise> for v1::Exercise {
+    type Error = ProtoError;
+
+    fn try_from(value: api::Exercise) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: value.id,
+            name: value.name,
+            description: value.description,
+            exercise_type: v1::ExerciseType::try_from(value.exercise_type)?.into(),
+            bar_weight: value.bar_weight.map(Into::into),
+            load_increment: value.load_increment.map(Into::into),
+        })
+    }
+}
+
+impl TryFrom<v1::Exercise> for api::Exercise {
+    type Error = ProtoError;
+
+    fn try_from(value: v1::Exercise) -> Result<Self, Self::Error> {
+        let exercise_type = v1::ExerciseType::try_from(value.exercise_type)
+            .map_err(|_| ProtoError::UnknownExerciseType(value.exercise_type))?;
+        Ok(Self {
+            id: value.id,
+            name: value.name,
+            description: value.description,
+            exercise_type: exercise_type.try_into()?,
+            bar_weight: value.bar_weight.map(TryInto::try_into).transpose()?,
+            load_increment: value.load_increment.map(TryInto::try_into).transpose()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use api::{Exercise, ExerciseType};
+    use prost::Message;
+
+    #[test]
+    fn exercise_type_matches_repository_values() {
+        for et in [
+            ExerciseType::Barbell,
+            ExerciseType::KettleBell,
+            ExerciseType::BodyWeight,
+        ] {
+            let pb = v1::ExerciseType::try_from(et).unwrap();
+            assert_eq!(pb as i64, i64::from(et) + 1);
+            assert_eq!(ExerciseType::try_from(pb).unwrap(), et);
+        }
+    }
+
+    #[test]
+    fn exercise_round_trip() {
+        let exercise = Exercise {
+            id: Some(7),
+            name: "Swing".to_string(),
+            description: None,
+            exercise_type: ExerciseType::KettleBell,
+            bar_weight: None,
+            load_increment: Some(api::Weight::lb(5.0)),
+        };
+        let bytes = v1::Exercise::try_from(exercise.clone())
+            .unwrap()
+            .encode_to_vec();
+        let decoded = v1::Exercise::decode(bytes.as_slice()).unwrap();
+        assert_eq!(Exercise::try_from(decoded).unwrap(), exercise);
+    }
+
+    #[test]
+    fn exercise_unknown_type() {
+        let pb = v1::Exercise {
+            id: None,
+            name: "Sled Push".to_string(),
+            description: None,
+            exercise_type: 9,
+            bar_weight: None,
+            load_increment: None,
+        };
+        assert_eq!(
+            Exercise::try_from(pb).err().unwrap(),
+            ProtoError::UnknownExerciseType(9)
+        );
+    }
+
+    #[test]
+    fn exercise_unspecified_type() {
+        // A message that never set the type decodes with the default of 0
+        let bytes = v1::Exercise {
+            id: None,
+            name: "Sled Push".to_string(),
+            description: None,
+            exercise_type: v1::ExerciseType::Unspecified.into(),
+            bar_weight: None,
+            load_increment: None,
+        }
+        .encode_to_vec();
+        let decoded = v1::Exercise::decode(bytes.as_slice()).unwrap();
+        assert_eq!(
+            Exercise::try_from(decoded).err().unwrap(),
+            ProtoError::UnknownExerciseType(0)
+        );
+    }
+
+    #[test]
+    fn weight_unspecified_unit() {
+        let pb = v1::Weight {
+            value: 20.0,
+            unit: v1::WeightUnit::Unspecified.into(),
+        };
+        assert_eq!(
+            api::Weight::try_from(pb).err().unwrap(),
+            ProtoError::UnknownWeightUnit(0)
+        );
+    }
+
+    #[test]
+    fn weight_unknown_unit() {
+        let pb = v1::Weight {
+            value: 20.0,
+            unit: 4,
+        };
+        assert_eq!(
+            api::Weight::try_from(pb).err().unwrap(),
+            ProtoError::UnknownWeightUnit(4)
+        );
+    }
+}
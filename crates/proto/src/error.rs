@@ -0,0 +1,14 @@
+pub type ProtoResult<T, E = ProtoError> = Result<T, E>;
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum ProtoError {
+    #[error("UnknownExerciseType: {0}")]
+    UnknownExerciseType(i32),
+
+    #[error("UnknownWeightUnit: {0}")]
+    UnknownWeightUnit(i32),
+
+    #[error("UnsupportedExerciseType: {0}")]
+    UnsupportedExerciseType(String),
+}
@@ -0,0 +1,9 @@
+mod convert;
+mod error;
+
+pub use self::error::*;
+
+/// Types generated from `proto/trainer/v1`.
+pub mod v1 {
+    include!(concat!(env!("OUT_DIR"), "/trainer.v1.rs"));
+}
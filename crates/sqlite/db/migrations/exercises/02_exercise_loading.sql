@@ -0,0 +1,3 @@
+-- Weights are stored as text, e.g. '20kg' or '5lb', so the unit they were given in is kept
+ALTER TABLE EXERCISE ADD COLUMN bar_weight TEXT;
+ALTER TABLE EXERCISE ADD COLUMN load_increment TEXT;
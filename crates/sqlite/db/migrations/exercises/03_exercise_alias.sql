@@ -0,0 +1,4 @@
+CREATE TABLE IF NOT EXISTS EXERCISE_ALIAS (
+    alias TEXT PRIMARY KEY COLLATE NOCASE,
+    exercise_id INTEGER NOT NULL REFERENCES EXERCISE (id)
+);
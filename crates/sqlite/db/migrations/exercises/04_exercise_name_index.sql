@@ -0,0 +1,2 @@
+-- Lets name prefix queries (LIKE 'x%') use an index instead of scanning the table
+CREATE INDEX IF NOT EXISTS EXERCISE_NAME_NOCASE ON EXERCISE (name COLLATE NOCASE);
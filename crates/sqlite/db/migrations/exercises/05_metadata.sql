@@ -0,0 +1,5 @@
+-- Application level facts about the database, e.g. the schema version that last opened it
+CREATE TABLE IF NOT EXISTS METADATA (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL
+);
@@ -0,0 +1,17 @@
+-- Dates are stored as ISO 8601 text, e.g. '2024-05-01', so they sort chronologically
+CREATE TABLE IF NOT EXISTS WORKOUT_SESSION (
+    id INTEGER PRIMARY KEY,
+    date TEXT NOT NULL,
+    name TEXT,
+    notes TEXT
+);
+CREATE INDEX IF NOT EXISTS WORKOUT_SESSION_DATE ON WORKOUT_SESSION (date);
+
+-- The exercises of a session in the order they were performed
+CREATE TABLE IF NOT EXISTS WORKOUT_SESSION_EXERCISE (
+    session_id INTEGER NOT NULL REFERENCES WORKOUT_SESSION (id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    exercise_id INTEGER NOT NULL REFERENCES EXERCISE (id),
+    notes TEXT,
+    PRIMARY KEY (session_id, position)
+);
@@ -0,0 +1,7 @@
+-- Custom numeric measurements of a session, one row per name
+CREATE TABLE IF NOT EXISTS WORKOUT_SESSION_METRIC (
+    session_id INTEGER NOT NULL REFERENCES WORKOUT_SESSION (id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    value REAL NOT NULL,
+    PRIMARY KEY (session_id, name)
+);
@@ -0,0 +1,11 @@
+-- Completed sets; weights are stored as text like the exercise loading rules, e.g. '140kg'
+CREATE TABLE IF NOT EXISTS WORKOUT_SET (
+    id INTEGER PRIMARY KEY,
+    session_id INTEGER NOT NULL REFERENCES WORKOUT_SESSION (id) ON DELETE CASCADE,
+    exercise_id INTEGER NOT NULL REFERENCES EXERCISE (id),
+    set_number INTEGER NOT NULL,
+    weight TEXT,
+    reps INTEGER NOT NULL,
+    rpe REAL,
+    UNIQUE (session_id, exercise_id, set_number)
+);
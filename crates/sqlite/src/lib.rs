@@ -1,48 +1,104 @@
-use api::Exercise;
 use api::ExerciseRepository;
-use api::RepositoryError::{ConnectionError, ItemNotFoundError, QueryError};
+use api::RepositoryError::{ConnectionError, ItemNotFoundError, QueryError, SchemaMismatchError};
+use api::{Exercise, ExerciseAlias, Weight};
 use api::{RepositoryError, RepositoryResult};
 use async_trait::async_trait;
-use sqlx::sqlite::{SqliteConnectOptions, SqliteRow};
-use sqlx::{migrate, Acquire, Error, Row, SqlitePool};
+use sqlx::migrate::{MigrateError, Migrator};
+use sqlx::sqlite::{SqliteConnectOptions, SqliteConnection, SqlitePoolOptions, SqliteRow};
+use sqlx::{migrate, Acquire, ConnectOptions, Error, Row, SqlitePool};
 use std::path::Path;
 use std::str::FromStr;
+use std::time::{Duration, Instant};
 use tracing::instrument;
 
+mod maintenance;
+mod storage;
+mod workout;
+
+pub use self::maintenance::*;
+pub use self::storage::*;
+
+static MIGRATOR: Migrator = migrate!("db/migrations/exercises");
+
+const SCHEMA_VERSION_KEY: &str = "schema_version";
+
+/// Version of the newest migration of this build.  It is recorded in the database so an older
+/// build refuses to write to a database a newer one has migrated.
+pub fn schema_version() -> i64 {
+    MIGRATOR.iter().map(|m| m.version).max().unwrap_or_default()
+}
+
 #[derive(Clone, Debug)]
 pub enum DBType<'a> {
     InMemory,
     File(&'a Path),
 }
 
+/// Limits applied to the statements the repository runs.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct QueryOptions {
+    /// Interrupts a statement still running after this long.  The clock starts when a connection
+    /// is taken from the pool, so it covers every statement of an operation run on one connection.
+    pub timeout: Option<Duration>,
+
+    /// Logs statements taking longer than this at WARN, with their SQL and duration, in the span
+    /// of the calling operation
+    pub slow_threshold: Option<Duration>,
+}
+
+/// Virtual machine instructions between checks of the timeout, a few microseconds of work.
+const PROGRESS_OPS: i32 = 1000;
+
 #[derive(Clone, Debug)]
 pub struct SqliteExerciseRepository {
     pool: SqlitePool,
 }
 
 impl SqliteExerciseRepository {
-    #[instrument]
     pub async fn new(dbtype: DBType<'_>) -> RepositoryResult<Self> {
-        let pool_result: Result<SqlitePool, Error> = match dbtype {
-            DBType::InMemory => SqlitePool::connect("sqlite::memory:").await,
-            DBType::File(f) => {
-                let opts = SqliteConnectOptions::from_str(
-                    format!("sqlite://{}", f.to_str().unwrap()).as_str(),
-                )
-                .unwrap()
-                .create_if_missing(true)
-                .foreign_keys(true);
+        Self::with_options(dbtype, QueryOptions::default()).await
+    }
 
-                SqlitePool::connect_with(opts).await
+    #[instrument]
+    pub async fn with_options(dbtype: DBType<'_>, options: QueryOptions) -> RepositoryResult<Self> {
+        let mut opts = match dbtype {
+            DBType::InMemory => SqliteConnectOptions::from_str("sqlite::memory:").unwrap(),
+            DBType::File(f) => {
+                SqliteConnectOptions::from_str(format!("sqlite://{}", f.to_str().unwrap()).as_str())
+                    .unwrap()
+                    .create_if_missing(true)
+                    .foreign_keys(true)
             }
         };
+        if let Some(threshold) = options.slow_threshold {
+            opts = opts.log_slow_statements(log::LevelFilter::Warn, threshold);
+        }
+
+        let mut pool = SqlitePoolOptions::new();
+        if let Some(timeout) = options.timeout {
+            // New connections skip before_acquire, so the deadline is also set after connecting
+            pool = pool
+                .after_connect(move |conn, _| {
+                    Box::pin(async move { set_deadline(conn, timeout).await })
+                })
+                .before_acquire(move |conn, _| {
+                    Box::pin(async move { set_deadline(conn, timeout).await.map(|_| true) })
+                });
+        }
+        let pool_result: Result<SqlitePool, Error> = pool.connect_with(opts).await;
 
         match pool_result {
             Ok(p) => {
-                let migrate_result = migrate!("db/migrations/exercises").run(&p).await;
-
-                match migrate_result {
-                    Ok(_) => Ok(Self { pool: p }),
+                check_schema_version(&p).await?;
+                match MIGRATOR.run(&p).await {
+                    Ok(_) => {
+                        record_schema_version(&p).await?;
+                        Ok(Self { pool: p })
+                    }
+                    Err(MigrateError::VersionMissing(version)) => Err(SchemaMismatchError {
+                        database: version,
+                        supported: schema_version(),
+                    }),
                     Err(e) => Err(ConnectionError(e.to_string())),
                 }
             }
@@ -50,16 +106,32 @@ impl SqliteExerciseRepository {
         }
     }
 
+    /// Opens an existing database file for reading only.  Unlike [`Self::new`] it neither creates
+    /// the file nor migrates it, for callers such as shell completion that must leave the
+    /// database untouched.
+    #[instrument]
+    pub async fn open_read_only(path: &Path) -> RepositoryResult<Self> {
+        let opts = SqliteConnectOptions::new()
+            .filename(path)
+            .read_only(true)
+            .foreign_keys(true);
+        match SqlitePoolOptions::new().connect_with(opts).await {
+            Ok(p) => Ok(Self { pool: p }),
+            Err(e) => Err(ConnectionError(e.to_string())),
+        }
+    }
+
     fn process_query(&self, r: Result<SqliteRow, Error>) -> RepositoryResult<Exercise> {
         match r {
             Ok(r) => {
-                println!("{:?}", r.len());
                 let et: i64 = r.get(3);
                 Ok(Exercise {
                     id: Some(r.get(0)),
                     name: r.get(1),
                     description: r.get(2),
                     exercise_type: i64::into(et),
+                    bar_weight: weight_column(&r, 4)?,
+                    load_increment: weight_column(&r, 5)?,
                 })
             }
             Err(e) => match e {
@@ -70,6 +142,84 @@ impl SqliteExerciseRepository {
     }
 }
 
+/// Interrupts whatever `conn` runs once `timeout` has passed.
+async fn set_deadline(conn: &mut SqliteConnection, timeout: Duration) -> Result<(), Error> {
+    let deadline = Instant::now() + timeout;
+    conn.lock_handle()
+        .await?
+        .set_progress_handler(PROGRESS_OPS, move || Instant::now() < deadline);
+    Ok(())
+}
+
+/// Fails when the database was last migrated by a newer build.  Databases created before the
+/// version was recorded have no METADATA table and always pass.
+async fn check_schema_version(pool: &SqlitePool) -> RepositoryResult<()> {
+    let has_metadata =
+        sqlx::query("SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'METADATA'")
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| ConnectionError(e.to_string()))?
+            .is_some();
+    if !has_metadata {
+        return Ok(());
+    }
+
+    let recorded: Option<String> = sqlx::query("SELECT value FROM METADATA WHERE key = ?1")
+        .bind(SCHEMA_VERSION_KEY)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| ConnectionError(e.to_string()))?
+        .map(|r| r.get(0));
+    match recorded.map(|v| v.parse::<i64>()) {
+        Some(Ok(version)) if version > schema_version() => Err(SchemaMismatchError {
+            database: version,
+            supported: schema_version(),
+        }),
+        Some(Err(e)) => Err(ConnectionError(format!("invalid schema version: {}", e))),
+        _ => Ok(()),
+    }
+}
+
+async fn record_schema_version(pool: &SqlitePool) -> RepositoryResult<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO METADATA (key, value) VALUES (?1, ?2)
+        ON CONFLICT (key) DO UPDATE SET value = excluded.value
+        WHERE CAST(value AS INTEGER) < CAST(excluded.value AS INTEGER)
+        "#,
+    )
+    .bind(SCHEMA_VERSION_KEY)
+    .bind(schema_version().to_string())
+    .execute(pool)
+    .await
+    .map_err(|e| ConnectionError(e.to_string()))?;
+    Ok(())
+}
+
+/// LIKE ignores ASCII case, matching the NOCASE index on the name so the prefix is looked up
+/// rather than scanned for.
+const PREFIX_QUERY: &str = r#"
+    SELECT id, name, description, exercise_type, bar_weight, load_increment
+    FROM EXERCISE WHERE deleted = 0 AND name LIKE ?1 ESCAPE '\'
+    ORDER BY name COLLATE NOCASE LIMIT ?2
+    "#;
+
+/// Tells a write clashing with the unique exercise name apart from other failures.
+fn write_error(err: sqlx::Error, name: &str) -> RepositoryError {
+    match err.as_database_error() {
+        Some(e) if e.is_unique_violation() => RepositoryError::DuplicateNameError(name.to_string()),
+        _ => RepositoryError::PersistenceError(err.to_string()),
+    }
+}
+
+fn weight_column(r: &SqliteRow, index: usize) -> RepositoryResult<Option<Weight>> {
+    let value: Option<String> = r.get(index);
+    value
+        .map(|v| v.parse::<Weight>())
+        .transpose()
+        .map_err(|e| QueryError(e.to_string()))
+}
+
 #[async_trait]
 impl ExerciseRepository for SqliteExerciseRepository {
     #[instrument(skip(self), fields(name = exercise.name))]
@@ -77,18 +227,21 @@ impl ExerciseRepository for SqliteExerciseRepository {
         let mut conn = self.pool.acquire().await.unwrap();
         let query_result = sqlx::query(
             r#"
-                INSERT INTO EXERCISE (name, description, exercise_type) VALUES (?1, ?2, ?3)
+                INSERT INTO EXERCISE (name, description, exercise_type, bar_weight, load_increment)
+                VALUES (?1, ?2, ?3, ?4, ?5)
                 "#,
         )
         .bind(&exercise.name)
         .bind(&exercise.description)
         .bind::<i64>(exercise.exercise_type.into())
+        .bind(exercise.bar_weight.map(|w| w.to_string()))
+        .bind(exercise.load_increment.map(|w| w.to_string()))
         .execute(&mut *conn)
         .await;
 
         match query_result {
             Ok(r) => Ok(r.last_insert_rowid()),
-            Err(e) => Err(RepositoryError::PersistenceError(e.to_string())),
+            Err(e) => Err(write_error(e, &exercise.name)),
         }
     }
 
@@ -99,12 +252,14 @@ impl ExerciseRepository for SqliteExerciseRepository {
         let update_result = sqlx::query(
             r#"
                 UPDATE EXERCISE set name = ?1, description = ?2,
-                exercise_type = ?3 WHERE id = ?4
+                exercise_type = ?3, bar_weight = ?4, load_increment = ?5 WHERE id = ?6
                 "#,
         )
         .bind(&exercise.name)
         .bind(&exercise.description)
         .bind::<i64>(exercise.exercise_type.into())
+        .bind(exercise.bar_weight.map(|w| w.to_string()))
+        .bind(exercise.load_increment.map(|w| w.to_string()))
         .bind(exercise.id)
         .execute(&mut *tx)
         .await;
@@ -125,7 +280,7 @@ impl ExerciseRepository for SqliteExerciseRepository {
                     }
                 }
             }
-            Err(e) => Err(RepositoryError::PersistenceError(e.to_string())),
+            Err(e) => Err(write_error(e, &exercise.name)),
         }
     }
 
@@ -134,7 +289,7 @@ impl ExerciseRepository for SqliteExerciseRepository {
         let mut conn = self.pool.acquire().await.unwrap();
         let query_result = sqlx::query(
             r#"
-                SELECT id, name, description, exercise_type
+                SELECT id, name, description, exercise_type, bar_weight, load_increment
                 FROM EXERCISE WHERE deleted = 0 AND
                 name = ?1 COLLATE NOCASE
                 "#,
@@ -151,7 +306,7 @@ impl ExerciseRepository for SqliteExerciseRepository {
         let mut conn = self.pool.acquire().await.unwrap();
         let query_result = sqlx::query(
             r#"
-                SELECT id, name, description, exercise_type
+                SELECT id, name, description, exercise_type, bar_weight, load_increment
                 FROM EXERCISE WHERE id = ?1 AND deleted = 0
                 "#,
         )
@@ -167,7 +322,7 @@ impl ExerciseRepository for SqliteExerciseRepository {
         let mut conn = self.pool.acquire().await.unwrap();
         let query_result = sqlx::query(
             r#"
-            SELECT id, name, description, exercise_type FROM
+            SELECT id, name, description, exercise_type, bar_weight, load_increment FROM
             EXERCISE WHERE DELETED = 0;
             "#,
         )
@@ -186,6 +341,34 @@ impl ExerciseRepository for SqliteExerciseRepository {
         }
     }
 
+    #[instrument(skip(self))]
+    async fn query_by_prefix(&self, prefix: String, limit: u32) -> RepositoryResult<Vec<Exercise>> {
+        let mut conn = self
+            .pool
+            .acquire()
+            .await
+            .map_err(|e| ConnectionError(e.to_string()))?;
+        let pattern = format!(
+            "{}%",
+            prefix
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_")
+        );
+        let query_result = sqlx::query(PREFIX_QUERY)
+            .bind(pattern)
+            .bind(limit)
+            .fetch_all(&mut *conn)
+            .await;
+        match query_result {
+            Ok(rows) => rows
+                .into_iter()
+                .map(|row| self.process_query(Ok(row)))
+                .collect(),
+            Err(err) => Err(QueryError(err.to_string())),
+        }
+    }
+
     #[instrument(skip(self), fields(id))]
     async fn delete(&self, id: i64) -> RepositoryResult<()> {
         let mut conn = self.pool.acquire().await.unwrap();
@@ -206,6 +389,110 @@ impl ExerciseRepository for SqliteExerciseRepository {
             Err(err) => Err(RepositoryError::DeleteError(err.to_string())),
         }
     }
+
+    #[instrument(skip(self))]
+    async fn list_deleted(&self) -> RepositoryResult<Vec<Exercise>> {
+        let mut conn = self
+            .pool
+            .acquire()
+            .await
+            .map_err(|e| ConnectionError(e.to_string()))?;
+        let query_result = sqlx::query(
+            r#"
+            SELECT id, name, description, exercise_type, bar_weight, load_increment FROM
+            EXERCISE WHERE DELETED = 1 ORDER BY name COLLATE NOCASE;
+            "#,
+        )
+        .fetch_all(&mut *conn)
+        .await;
+        match query_result {
+            Ok(rows) => rows
+                .into_iter()
+                .map(|row| self.process_query(Ok(row)))
+                .collect(),
+            Err(err) => Err(QueryError(err.to_string())),
+        }
+    }
+
+    #[instrument(skip(self), fields(id))]
+    async fn restore(&self, id: i64) -> RepositoryResult<()> {
+        let mut conn = self
+            .pool
+            .acquire()
+            .await
+            .map_err(|e| ConnectionError(e.to_string()))?;
+        let update_result = sqlx::query(
+            r#"
+            UPDATE EXERCISE SET deleted = 0 WHERE id = ?1 AND deleted = 1
+        "#,
+        )
+        .bind(id)
+        .execute(&mut *conn)
+        .await;
+        match update_result {
+            Ok(result) => match result.rows_affected() {
+                0 => Err(ItemNotFoundError),
+                _ => Ok(()),
+            },
+            Err(err) => Err(RepositoryError::PersistenceError(err.to_string())),
+        }
+    }
+
+    #[instrument(skip(self), fields(alias = alias.alias))]
+    async fn create_alias(&self, alias: &ExerciseAlias) -> RepositoryResult<()> {
+        let mut conn = self
+            .pool
+            .acquire()
+            .await
+            .map_err(|e| ConnectionError(e.to_string()))?;
+        let query_result = sqlx::query(
+            r#"
+                INSERT INTO EXERCISE_ALIAS (alias, exercise_id)
+                SELECT ?1, id FROM EXERCISE WHERE id = ?2 AND deleted = 0
+                ON CONFLICT (alias) DO UPDATE SET exercise_id = excluded.exercise_id
+                "#,
+        )
+        .bind(&alias.alias)
+        .bind(alias.exercise_id)
+        .execute(&mut *conn)
+        .await;
+
+        match query_result {
+            Ok(r) => match r.rows_affected() {
+                0 => Err(ItemNotFoundError),
+                _ => Ok(()),
+            },
+            Err(e) => Err(RepositoryError::PersistenceError(e.to_string())),
+        }
+    }
+
+    #[instrument(skip(self))]
+    async fn list_aliases(&self) -> RepositoryResult<Vec<ExerciseAlias>> {
+        let mut conn = self
+            .pool
+            .acquire()
+            .await
+            .map_err(|e| ConnectionError(e.to_string()))?;
+        let query_result = sqlx::query(
+            r#"
+            SELECT a.alias, a.exercise_id FROM EXERCISE_ALIAS a
+            JOIN EXERCISE e ON e.id = a.exercise_id WHERE e.deleted = 0
+            ORDER BY a.alias
+            "#,
+        )
+        .fetch_all(&mut *conn)
+        .await;
+        match query_result {
+            Ok(rows) => Ok(rows
+                .iter()
+                .map(|r| ExerciseAlias {
+                    alias: r.get(0),
+                    exercise_id: r.get(1),
+                })
+                .collect()),
+            Err(err) => Err(QueryError(err.to_string())),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -215,12 +502,12 @@ mod tests {
     use rand::{thread_rng, Rng};
 
     use api::exercise::ExerciseType::{Barbell, KettleBell};
-    use api::RepositoryError::{ConnectionError, PersistenceError};
+    use api::RepositoryError::{ConnectionError, DuplicateNameError, PersistenceError};
     use tempfile::tempdir;
     use test_log::test;
     use tokio::fs;
 
-    fn db_name() -> String {
+    pub(crate) fn db_name() -> String {
         let rand_string: String = thread_rng()
             .sample_iter(&Alphanumeric)
             .take(10)
@@ -230,12 +517,14 @@ mod tests {
         format!("testdb-{}.db3", rand_string)
     }
 
-    fn deadlift(id: Option<i64>) -> Exercise {
+    pub(crate) fn deadlift(id: Option<i64>) -> Exercise {
         Exercise {
             id,
             name: "Deadlift".to_string(),
             description: None,
             exercise_type: Barbell,
+            bar_weight: None,
+            load_increment: None,
         }
     }
 
@@ -245,6 +534,8 @@ mod tests {
             name: "Benchpress".to_string(),
             description: None,
             exercise_type: Barbell,
+            bar_weight: None,
+            load_increment: None,
         }
     }
 
@@ -254,6 +545,8 @@ mod tests {
             name: "Squat".to_string(),
             description: None,
             exercise_type: Barbell,
+            bar_weight: None,
+            load_increment: None,
         }
     }
 
@@ -263,6 +556,33 @@ mod tests {
         assert!(repo.is_ok())
     }
 
+    #[test(tokio::test)]
+    async fn query_timeout() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join(db_name());
+        let options = QueryOptions {
+            timeout: Some(Duration::from_millis(50)),
+            slow_threshold: Some(Duration::from_millis(10)),
+        };
+        let repo =
+            SqliteExerciseRepository::with_options(DBType::File(file_path.as_path()), options)
+                .await
+                .unwrap();
+        repo.create(&deadlift(None)).await.unwrap();
+
+        // Counts to a billion, far more than fits in the timeout
+        let result = sqlx::query(
+            "WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < 1000000000) SELECT COUNT(*) FROM n",
+        )
+        .fetch_one(&repo.pool)
+        .await;
+        assert!(result.err().unwrap().to_string().contains("interrupted"));
+
+        // Every acquire gets a fresh deadline
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(repo.list().await.unwrap().len(), 1);
+    }
+
     #[test(tokio::test)]
     async fn test_new_file_connection() {
         let dir = tempdir().unwrap();
@@ -283,6 +603,43 @@ mod tests {
         ))
     }
 
+    #[test(tokio::test)]
+    async fn open_read_only() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join(db_name());
+        let result = SqliteExerciseRepository::open_read_only(&file_path).await;
+        assert!(matches!(result.err().unwrap(), ConnectionError(_)));
+        assert!(!file_path.exists());
+
+        let repo = SqliteExerciseRepository::new(DBType::File(file_path.as_path()))
+            .await
+            .unwrap();
+        repo.create(&deadlift(None)).await.unwrap();
+        repo.pool.close().await;
+
+        let repo = SqliteExerciseRepository::open_read_only(&file_path)
+            .await
+            .unwrap();
+        assert_eq!(repo.list().await.unwrap().len(), 1);
+        assert!(matches!(
+            repo.create(&squat(None)).await.err().unwrap(),
+            PersistenceError(_)
+        ));
+    }
+
+    #[test(tokio::test)]
+    async fn open_read_only_does_not_migrate() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join(db_name());
+        std::fs::File::create(&file_path).unwrap();
+
+        let repo = SqliteExerciseRepository::open_read_only(&file_path)
+            .await
+            .unwrap();
+        assert!(repo.list().await.is_err());
+        assert_eq!(std::fs::metadata(&file_path).unwrap().len(), 0);
+    }
+
     #[test(tokio::test)]
     async fn create_ok() {
         let dir = tempdir().unwrap();
@@ -437,6 +794,250 @@ mod tests {
         );
     }
 
+    #[test(tokio::test)]
+    async fn create_and_update_loading_rules() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join(db_name());
+        let repo = SqliteExerciseRepository::new(DBType::File(file_path.as_path()))
+            .await
+            .unwrap();
+
+        let mut e = deadlift(None);
+        e.bar_weight = Some(Weight::kg(20.0));
+        e.load_increment = Some(Weight::lb(5.0));
+        let id = repo.create(&e).await.unwrap();
+
+        let mut found_ex = repo.query_by_id(id).await.unwrap();
+        assert_eq!(found_ex.bar_weight, Some(Weight::kg(20.0)));
+        assert_eq!(found_ex.load_increment, Some(Weight::lb(5.0)));
+
+        found_ex.bar_weight = None;
+        found_ex.load_increment = Some(Weight::kg(1.25));
+        repo.update(&found_ex).await.unwrap();
+
+        let found_ex = repo.query_by_name("deadlift".to_string()).await.unwrap();
+        assert_eq!(found_ex.bar_weight, None);
+        assert_eq!(found_ex.load_increment, Some(Weight::kg(1.25)));
+    }
+
+    #[test(tokio::test)]
+    async fn schema_version_recorded() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join(db_name());
+        let repo = SqliteExerciseRepository::new(DBType::File(file_path.as_path()))
+            .await
+            .unwrap();
+        let recorded: String =
+            sqlx::query("SELECT value FROM METADATA WHERE key = 'schema_version'")
+                .fetch_one(&repo.pool)
+                .await
+                .unwrap()
+                .get(0);
+        assert_eq!(recorded, schema_version().to_string());
+
+        // Reopening with the same build is fine
+        repo.pool.close().await;
+        assert!(
+            SqliteExerciseRepository::new(DBType::File(file_path.as_path()))
+                .await
+                .is_ok()
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn newer_schema_version_rejected() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join(db_name());
+        let repo = SqliteExerciseRepository::new(DBType::File(file_path.as_path()))
+            .await
+            .unwrap();
+        sqlx::query("UPDATE METADATA SET value = '99' WHERE key = 'schema_version'")
+            .execute(&repo.pool)
+            .await
+            .unwrap();
+        repo.pool.close().await;
+
+        let result = SqliteExerciseRepository::new(DBType::File(file_path.as_path())).await;
+        assert!(matches!(
+            result.err().unwrap(),
+            SchemaMismatchError { database: 99, supported } if supported == schema_version()
+        ));
+    }
+
+    #[test(tokio::test)]
+    async fn unknown_migration_rejected() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join(db_name());
+        let repo = SqliteExerciseRepository::new(DBType::File(file_path.as_path()))
+            .await
+            .unwrap();
+        // A migration applied by a newer build that predates the recorded version
+        sqlx::query(
+            "INSERT INTO _sqlx_migrations (version, description, success, checksum, execution_time) \
+             VALUES (42, 'future', 1, x'00', 0)",
+        )
+        .execute(&repo.pool)
+        .await
+        .unwrap();
+        sqlx::query("DELETE FROM METADATA")
+            .execute(&repo.pool)
+            .await
+            .unwrap();
+        repo.pool.close().await;
+
+        let result = SqliteExerciseRepository::new(DBType::File(file_path.as_path())).await;
+        assert!(matches!(
+            result.err().unwrap(),
+            SchemaMismatchError { database: 42, .. }
+        ));
+    }
+
+    #[test(tokio::test)]
+    async fn query_by_prefix_ok() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join(db_name());
+        let repo = SqliteExerciseRepository::new(DBType::File(file_path.as_path()))
+            .await
+            .unwrap();
+        for name in [
+            "Deadlift",
+            "dips",
+            "Squat",
+            "Deficit_Deadlift",
+            "100% Squat",
+        ] {
+            repo.create(&Exercise {
+                name: name.to_string(),
+                ..deadlift(None)
+            })
+            .await
+            .unwrap();
+        }
+        let dips_id = repo.query_by_name("dips".to_string()).await.unwrap().id;
+        repo.delete(dips_id.unwrap()).await.unwrap();
+
+        let names = |exercises: Vec<Exercise>| -> Vec<String> {
+            exercises.into_iter().map(|e| e.name).collect()
+        };
+        assert_eq!(
+            names(repo.query_by_prefix("DE".to_string(), 10).await.unwrap()),
+            vec!["Deadlift", "Deficit_Deadlift"]
+        );
+        assert_eq!(
+            names(repo.query_by_prefix("de".to_string(), 1).await.unwrap()),
+            vec!["Deadlift"]
+        );
+        // Wildcards in the prefix are matched literally
+        assert_eq!(
+            names(repo.query_by_prefix("Def_".to_string(), 10).await.unwrap()),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            names(repo.query_by_prefix("100%".to_string(), 10).await.unwrap()),
+            vec!["100% Squat"]
+        );
+        assert_eq!(
+            repo.query_by_prefix(String::new(), 10).await.unwrap().len(),
+            4
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn query_by_prefix_uses_index() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join(db_name());
+        let repo = SqliteExerciseRepository::new(DBType::File(file_path.as_path()))
+            .await
+            .unwrap();
+        // A SCAN of the index, e.g. for the ORDER BY, would mean the prefix is not looked up
+        for pattern in ["de%", "1\\_rm%"] {
+            let plan: Vec<String> = sqlx::query(&format!("EXPLAIN QUERY PLAN {}", PREFIX_QUERY))
+                .bind(pattern)
+                .bind(10)
+                .fetch_all(&repo.pool)
+                .await
+                .unwrap()
+                .iter()
+                .map(|r| r.get::<String, _>(3))
+                .collect();
+            assert_eq!(
+                plan,
+                vec!["SEARCH EXERCISE USING INDEX EXERCISE_NAME_NOCASE (name>? AND name<?)"],
+                "{}",
+                pattern
+            );
+        }
+    }
+
+    #[test(tokio::test)]
+    async fn list_deleted_and_restore() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join(db_name());
+        let repo = SqliteExerciseRepository::new(DBType::File(file_path.as_path()))
+            .await
+            .unwrap();
+        let deadlift_id = repo.create(&deadlift(None)).await.unwrap();
+        let squat_id = repo.create(&squat(None)).await.unwrap();
+        repo.create(&benchpress(None)).await.unwrap();
+        assert!(repo.list_deleted().await.unwrap().is_empty());
+
+        repo.delete(squat_id).await.unwrap();
+        repo.delete(deadlift_id).await.unwrap();
+        assert_eq!(
+            repo.list_deleted().await.unwrap(),
+            vec![deadlift(Some(deadlift_id)), squat(Some(squat_id))]
+        );
+
+        repo.restore(squat_id).await.unwrap();
+        assert_eq!(
+            repo.query_by_id(squat_id).await.unwrap(),
+            squat(Some(squat_id))
+        );
+        assert_eq!(repo.list_deleted().await.unwrap().len(), 1);
+        assert_eq!(repo.list().await.unwrap().len(), 2);
+
+        // Only deleted exercises can be restored
+        let result = repo.restore(squat_id).await;
+        assert!(matches!(result.err().unwrap(), ItemNotFoundError));
+        let result = repo.restore(1000).await;
+        assert!(matches!(result.err().unwrap(), ItemNotFoundError));
+    }
+
+    #[test(tokio::test)]
+    async fn create_and_list_aliases() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join(db_name());
+        let repo = SqliteExerciseRepository::new(DBType::File(file_path.as_path()))
+            .await
+            .unwrap();
+        let deadlift_id = repo.create(&deadlift(None)).await.unwrap();
+        let squat_id = repo.create(&squat(None)).await.unwrap();
+
+        let alias = |alias: &str, exercise_id| ExerciseAlias {
+            alias: alias.to_string(),
+            exercise_id,
+        };
+        repo.create_alias(&alias("DL", squat_id)).await.unwrap();
+        repo.create_alias(&alias("Back Squat", squat_id))
+            .await
+            .unwrap();
+        // Adding an alias again corrects the exercise it points to
+        repo.create_alias(&alias("dl", deadlift_id)).await.unwrap();
+        assert_eq!(
+            repo.list_aliases().await.unwrap(),
+            vec![alias("Back Squat", squat_id), alias("DL", deadlift_id)]
+        );
+
+        let missing = repo.create_alias(&alias("Bench", 1000)).await;
+        assert!(matches!(missing.err().unwrap(), ItemNotFoundError));
+
+        repo.delete(squat_id).await.unwrap();
+        assert_eq!(
+            repo.list_aliases().await.unwrap(),
+            vec![alias("DL", deadlift_id)]
+        );
+    }
+
     #[test(tokio::test)]
     async fn update_not_found() {
         let dir = tempdir().unwrap();
@@ -483,8 +1084,31 @@ mod tests {
 
         let same_ex = deadlift(None);
         let result = repo.create(&same_ex).await;
-        assert!(result.is_err());
-        assert!(matches!(result.err().unwrap(), PersistenceError(_)))
+        assert!(matches!(result, Err(DuplicateNameError(name)) if name == same_ex.name));
+
+        // Deleted exercises keep their name
+        let id = repo.create(&benchpress(None)).await.unwrap();
+        repo.delete(id).await.unwrap();
+        let result = repo.create(&benchpress(None)).await;
+        assert!(matches!(result, Err(DuplicateNameError(_))));
+    }
+
+    #[test(tokio::test)]
+    async fn update_duplicate_name() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join(db_name());
+        let repo = SqliteExerciseRepository::new(DBType::File(file_path.as_path()))
+            .await
+            .unwrap();
+
+        repo.create(&deadlift(None)).await.unwrap();
+        let id = repo.create(&benchpress(None)).await.unwrap();
+        let renamed = Exercise {
+            name: deadlift(None).name,
+            ..benchpress(Some(id))
+        };
+        let result = repo.update(&renamed).await;
+        assert!(matches!(result, Err(DuplicateNameError(_))));
     }
 
     #[test(tokio::test)]
@@ -543,7 +1167,7 @@ mod tests {
             .unwrap();
         let dl = deadlift(None);
         let id = repo.create(&dl).await.unwrap();
-        let delete_result = repo.delete(id.clone()).await;
+        let delete_result = repo.delete(id).await;
         assert!(delete_result.is_ok());
 
         //Make sure the items is not returned
@@ -0,0 +1,246 @@
+use crate::SqliteExerciseRepository;
+use api::RepositoryError::{PersistenceError, QueryError};
+use api::RepositoryResult;
+use serde::Serialize;
+use sqlx::Row;
+use tracing::{info, instrument, warn};
+
+/// Row count of a single table.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct TableStats {
+    pub name: String,
+    pub rows: u64,
+}
+
+/// How large the database is and where the space goes.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct DatabaseStats {
+    /// Size of the database in bytes, excluding the write-ahead log
+    pub size: u64,
+
+    /// Size of the write-ahead log in bytes, 0 when there is none
+    pub wal_size: u64,
+
+    pub tables: Vec<TableStats>,
+}
+
+/// The outcome of [`SqliteExerciseRepository::maintain`].
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct MaintenanceReport {
+    /// Problems found by `PRAGMA integrity_check`, empty when the database is intact
+    pub integrity_errors: Vec<String>,
+
+    /// Whether `ANALYZE` and `VACUUM` ran, which they only do on an intact database
+    pub optimized: bool,
+
+    /// Size of the database in bytes before and after the vacuum
+    pub size_before: u64,
+    pub size_after: u64,
+}
+
+impl SqliteExerciseRepository {
+    /// Runs `PRAGMA integrity_check`, returning the problems it finds.
+    #[instrument(skip(self))]
+    pub async fn integrity_check(&self) -> RepositoryResult<Vec<String>> {
+        let rows = sqlx::query("PRAGMA integrity_check")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| QueryError(e.to_string()))?;
+        let messages: Vec<String> = rows.iter().map(|r| r.get(0)).collect();
+        Ok(match messages.as_slice() {
+            [ok] if ok == "ok" => vec![],
+            _ => messages,
+        })
+    }
+
+    /// Refreshes the statistics the query planner uses to pick indexes.
+    #[instrument(skip(self))]
+    pub async fn analyze(&self) -> RepositoryResult<()> {
+        sqlx::query("ANALYZE")
+            .execute(&self.pool)
+            .await
+            .map_err(|e| PersistenceError(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Rebuilds the database file, returning the space of deleted rows to the file system.
+    #[instrument(skip(self))]
+    pub async fn vacuum(&self) -> RepositoryResult<()> {
+        sqlx::query("VACUUM")
+            .execute(&self.pool)
+            .await
+            .map_err(|e| PersistenceError(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Size of the database in bytes, excluding the write-ahead log.
+    #[instrument(skip(self))]
+    pub async fn size(&self) -> RepositoryResult<u64> {
+        let row = sqlx::query(
+            "SELECT page_count * page_size FROM pragma_page_count(), pragma_page_size()",
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| QueryError(e.to_string()))?;
+        Ok(row.get::<i64, _>(0) as u64)
+    }
+
+    /// Collects the size of the database and its write-ahead log and the row count of each table.
+    #[instrument(skip(self))]
+    pub async fn stats(&self) -> RepositoryResult<DatabaseStats> {
+        let names: Vec<String> = sqlx::query(
+            "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| QueryError(e.to_string()))?
+        .iter()
+        .map(|r| r.get(0))
+        .collect();
+
+        let mut tables = Vec::with_capacity(names.len());
+        for name in names {
+            let count: i64 = sqlx::query(&format!(
+                "SELECT COUNT(*) FROM \"{}\"",
+                name.replace('"', "\"\"")
+            ))
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| QueryError(e.to_string()))?
+            .get(0);
+            tables.push(TableStats {
+                name,
+                rows: count as u64,
+            });
+        }
+
+        Ok(DatabaseStats {
+            size: self.size().await?,
+            wal_size: self.wal_size().await?,
+            tables,
+        })
+    }
+
+    /// The write-ahead log lives next to the database file, so an in-memory database has none.
+    async fn wal_size(&self) -> RepositoryResult<u64> {
+        let file: String = sqlx::query("SELECT file FROM pragma_database_list WHERE name = 'main'")
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| QueryError(e.to_string()))?
+            .get(0);
+        if file.is_empty() {
+            return Ok(0);
+        }
+        match tokio::fs::metadata(format!("{}-wal", file)).await {
+            Ok(metadata) => Ok(metadata.len()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(0),
+            Err(e) => Err(QueryError(e.to_string())),
+        }
+    }
+
+    /// Checks the integrity of the database and, when it is intact, analyzes and vacuums it.  A
+    /// damaged database is left untouched so it can be recovered from.
+    #[instrument(skip(self))]
+    pub async fn maintain(&self) -> RepositoryResult<MaintenanceReport> {
+        let size_before = self.size().await?;
+        let integrity_errors = self.integrity_check().await?;
+        let optimized = integrity_errors.is_empty();
+        if optimized {
+            self.analyze().await?;
+            self.vacuum().await?;
+            info!("database analyzed and vacuumed");
+        } else {
+            warn!(
+                "integrity check found {} problems, skipping optimization",
+                integrity_errors.len()
+            );
+        }
+
+        Ok(MaintenanceReport {
+            integrity_errors,
+            optimized,
+            size_before,
+            size_after: self.size().await?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{db_name, deadlift};
+    use crate::{DBType, SqliteExerciseRepository};
+    use api::{Exercise, ExerciseRepository};
+    use tempfile::tempdir;
+    use test_log::test;
+
+    #[test(tokio::test)]
+    async fn maintain_ok() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join(db_name());
+        let repo = SqliteExerciseRepository::new(DBType::File(file_path.as_path()))
+            .await
+            .unwrap();
+        for i in 0..200 {
+            repo.create(&Exercise {
+                name: format!("Exercise {}", i),
+                description: Some("x".repeat(500)),
+                ..deadlift(None)
+            })
+            .await
+            .unwrap();
+        }
+        sqlx::query("DELETE FROM EXERCISE")
+            .execute(&repo.pool)
+            .await
+            .unwrap();
+
+        let report = repo.maintain().await.unwrap();
+        assert!(report.integrity_errors.is_empty());
+        assert!(report.optimized);
+        assert!(report.size_after < report.size_before, "{:?}", report);
+        assert_eq!(report.size_after, repo.size().await.unwrap());
+    }
+
+    #[test(tokio::test)]
+    async fn stats_ok() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join(db_name());
+        let repo = SqliteExerciseRepository::new(DBType::File(file_path.as_path()))
+            .await
+            .unwrap();
+        repo.create(&deadlift(None)).await.unwrap();
+
+        let stats = repo.stats().await.unwrap();
+        assert_eq!(stats.size, repo.size().await.unwrap());
+        assert_eq!(stats.wal_size, 0);
+        let rows = |table: &str| {
+            stats
+                .tables
+                .iter()
+                .find(|t| t.name == table)
+                .map(|t| t.rows)
+        };
+        assert_eq!(rows("EXERCISE"), Some(1));
+        assert_eq!(rows("EXERCISE_ALIAS"), Some(0));
+        assert_eq!(rows("METADATA"), Some(1));
+        assert!(stats.tables.windows(2).all(|w| w[0].name < w[1].name));
+    }
+
+    #[test(tokio::test)]
+    async fn stats_in_memory() {
+        let repo = SqliteExerciseRepository::new(DBType::InMemory)
+            .await
+            .unwrap();
+        assert_eq!(repo.stats().await.unwrap().wal_size, 0);
+    }
+
+    #[test(tokio::test)]
+    async fn integrity_check_ok() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join(db_name());
+        let repo = SqliteExerciseRepository::new(DBType::File(file_path.as_path()))
+            .await
+            .unwrap();
+        assert!(repo.integrity_check().await.unwrap().is_empty());
+    }
+}
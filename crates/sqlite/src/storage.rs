@@ -0,0 +1,91 @@
+use std::ffi::OsString;
+use std::path::PathBuf;
+
+/// File name of the database inside the data directory.
+pub const DEFAULT_DB_FILE: &str = "trainer.db3";
+
+/// Where the database lives when none is configured, so every trainer frontend finds the same
+/// file: `$XDG_DATA_HOME/trainer` (falling back to `~/.local/share/trainer`) on Linux and other
+/// unixes, `~/Library/Application Support/trainer` on macOS and `%APPDATA%\trainer` on Windows.
+/// `None` when the home directory can't be determined.
+pub fn default_db_path() -> Option<PathBuf> {
+    data_dir(std::env::consts::OS, |name| std::env::var_os(name))
+        .map(|dir| dir.join("trainer").join(DEFAULT_DB_FILE))
+}
+
+fn data_dir<F: Fn(&str) -> Option<OsString>>(os: &str, var: F) -> Option<PathBuf> {
+    let non_empty = |name: &str| var(name).filter(|v| !v.is_empty()).map(PathBuf::from);
+    match os {
+        "windows" => non_empty("APPDATA"),
+        "macos" => non_empty("HOME").map(|home| home.join("Library").join("Application Support")),
+        _ => non_empty("XDG_DATA_HOME")
+            .or_else(|| non_empty("HOME").map(|home| home.join(".local").join("share"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<OsString> {
+        let vars: HashMap<String, OsString> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), OsString::from(v)))
+            .collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    #[test]
+    fn data_dir_linux() {
+        assert_eq!(
+            data_dir("linux", env(&[("HOME", "/home/me")])),
+            Some(PathBuf::from("/home/me/.local/share"))
+        );
+        assert_eq!(
+            data_dir(
+                "linux",
+                env(&[("HOME", "/home/me"), ("XDG_DATA_HOME", "/data")])
+            ),
+            Some(PathBuf::from("/data"))
+        );
+        assert_eq!(
+            data_dir(
+                "freebsd",
+                env(&[("HOME", "/home/me"), ("XDG_DATA_HOME", "")])
+            ),
+            Some(PathBuf::from("/home/me/.local/share"))
+        );
+        assert_eq!(data_dir("linux", env(&[])), None);
+    }
+
+    #[test]
+    fn data_dir_macos() {
+        assert_eq!(
+            data_dir(
+                "macos",
+                env(&[("HOME", "/Users/me"), ("XDG_DATA_HOME", "/data")])
+            ),
+            Some(PathBuf::from("/Users/me/Library/Application Support"))
+        );
+    }
+
+    #[test]
+    fn data_dir_windows() {
+        assert_eq!(
+            data_dir(
+                "windows",
+                env(&[("APPDATA", r"C:\Users\me\AppData\Roaming")])
+            ),
+            Some(PathBuf::from(r"C:\Users\me\AppData\Roaming"))
+        );
+        assert_eq!(data_dir("windows", env(&[("HOME", "/home/me")])), None);
+    }
+
+    #[test]
+    fn default_db_path_file_name() {
+        if let Some(path) = default_db_path() {
+            assert!(path.ends_with(PathBuf::from("trainer").join(DEFAULT_DB_FILE)));
+        }
+    }
+}
@@ -0,0 +1,916 @@
+use crate::{weight_column, SqliteExerciseRepository};
+use api::RepositoryError::{
+    ConnectionError, DeleteError, ItemNotFoundError, PersistenceError, QueryError,
+};
+use api::{
+    NaiveDate, PerformedExercise, RepositoryResult, Set, WorkoutSession, WorkoutSessionRepository,
+};
+use async_trait::async_trait;
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Acquire, Connection, Error, Row, SqliteConnection};
+use std::collections::{BTreeMap, HashMap};
+use tracing::instrument;
+
+const SESSION_COLUMNS: &str = "id, date, name, notes";
+
+fn session_row(r: &SqliteRow) -> RepositoryResult<WorkoutSession> {
+    let date: String = r.get(1);
+    Ok(WorkoutSession {
+        id: Some(r.get(0)),
+        date: date
+            .parse::<NaiveDate>()
+            .map_err(|e| QueryError(format!("invalid date '{}': {}", date, e)))?,
+        name: r.get(2),
+        notes: r.get(3),
+        exercises: vec![],
+        metrics: BTreeMap::new(),
+    })
+}
+
+const SET_COLUMNS: &str = "id, session_id, exercise_id, set_number, weight, reps, rpe";
+
+fn set_row(r: &SqliteRow) -> RepositoryResult<Set> {
+    Ok(Set {
+        id: Some(r.get(0)),
+        session_id: r.get(1),
+        exercise_id: r.get(2),
+        set_number: r.get(3),
+        weight: weight_column(r, 4)?,
+        reps: r.get(5),
+        rpe: r.get(6),
+    })
+}
+
+fn query_error(e: Error) -> api::RepositoryError {
+    match e {
+        Error::RowNotFound => ItemNotFoundError,
+        e => QueryError(e.to_string()),
+    }
+}
+
+/// Writes the performed exercises, numbered in order, and the metrics of the session `id`.
+async fn insert_children(
+    conn: &mut SqliteConnection,
+    id: i64,
+    session: &WorkoutSession,
+) -> Result<(), Error> {
+    for (position, exercise) in session.exercises.iter().enumerate() {
+        sqlx::query(
+            r#"
+            INSERT INTO WORKOUT_SESSION_EXERCISE (session_id, position, exercise_id, notes)
+            VALUES (?1, ?2, ?3, ?4)
+            "#,
+        )
+        .bind(id)
+        .bind(position as i64)
+        .bind(exercise.exercise_id)
+        .bind(&exercise.notes)
+        .execute(&mut *conn)
+        .await?;
+    }
+    for (name, value) in &session.metrics {
+        sqlx::query(
+            "INSERT INTO WORKOUT_SESSION_METRIC (session_id, name, value) VALUES (?1, ?2, ?3)",
+        )
+        .bind(id)
+        .bind(name)
+        .bind(value)
+        .execute(&mut *conn)
+        .await?;
+    }
+    Ok(())
+}
+
+/// Appends `exercise_id` to the performed exercises of the session `session_id` unless it is
+/// already one of them.
+async fn add_performed_exercise(
+    conn: &mut SqliteConnection,
+    session_id: i64,
+    exercise_id: i64,
+) -> Result<(), Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO WORKOUT_SESSION_EXERCISE (session_id, position, exercise_id)
+        SELECT ?1, (
+            SELECT COALESCE(MAX(position) + 1, 0) FROM WORKOUT_SESSION_EXERCISE
+            WHERE session_id = ?1
+        ), ?2
+        WHERE NOT EXISTS (
+            SELECT 1 FROM WORKOUT_SESSION_EXERCISE WHERE session_id = ?1 AND exercise_id = ?2
+        )
+        "#,
+    )
+    .bind(session_id)
+    .bind(exercise_id)
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
+
+/// Limits a query of sessions, or of the rows of their `session_id`, to those recording the
+/// metric bound to `?1`, or to every session when it is NULL.
+const METRIC_FILTER: &str =
+    "?1 IS NULL OR {id} IN (SELECT session_id FROM WORKOUT_SESSION_METRIC WHERE name = ?1)";
+
+impl SqliteExerciseRepository {
+    /// Sessions newest first, optionally only those recording `metric`.  The exercises and metrics
+    /// are read with one query each rather than one per session.
+    async fn sessions(&self, metric: Option<&str>) -> RepositoryResult<Vec<WorkoutSession>> {
+        let mut conn = self
+            .pool
+            .acquire()
+            .await
+            .map_err(|e| ConnectionError(e.to_string()))?;
+        let mut sessions = sqlx::query(&format!(
+            "SELECT {} FROM WORKOUT_SESSION WHERE {} ORDER BY date DESC, id DESC",
+            SESSION_COLUMNS,
+            METRIC_FILTER.replace("{id}", "id")
+        ))
+        .bind(metric)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(query_error)?
+        .iter()
+        .map(session_row)
+        .collect::<RepositoryResult<Vec<_>>>()?;
+
+        let mut exercises: HashMap<i64, Vec<PerformedExercise>> = HashMap::new();
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT session_id, exercise_id, notes FROM WORKOUT_SESSION_EXERCISE
+            WHERE {} ORDER BY session_id, position
+            "#,
+            METRIC_FILTER.replace("{id}", "session_id")
+        ))
+        .bind(metric)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(query_error)?;
+        for r in rows {
+            exercises
+                .entry(r.get(0))
+                .or_default()
+                .push(PerformedExercise {
+                    exercise_id: r.get(1),
+                    notes: r.get(2),
+                });
+        }
+
+        let mut metrics: HashMap<i64, BTreeMap<String, f64>> = HashMap::new();
+        let rows = sqlx::query(&format!(
+            "SELECT session_id, name, value FROM WORKOUT_SESSION_METRIC WHERE {}",
+            METRIC_FILTER.replace("{id}", "session_id")
+        ))
+        .bind(metric)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(query_error)?;
+        for r in rows {
+            metrics
+                .entry(r.get(0))
+                .or_default()
+                .insert(r.get(1), r.get(2));
+        }
+
+        for session in &mut sessions {
+            let id = session.id.unwrap_or_default();
+            session.exercises = exercises.remove(&id).unwrap_or_default();
+            session.metrics = metrics.remove(&id).unwrap_or_default();
+        }
+        Ok(sessions)
+    }
+}
+
+#[async_trait]
+impl WorkoutSessionRepository for SqliteExerciseRepository {
+    #[instrument(skip(self), fields(date = %session.date))]
+    async fn create_session(&self, session: &WorkoutSession) -> RepositoryResult<i64> {
+        let mut conn = self
+            .pool
+            .acquire()
+            .await
+            .map_err(|e| ConnectionError(e.to_string()))?;
+        let mut tx = conn
+            .begin()
+            .await
+            .map_err(|e| PersistenceError(e.to_string()))?;
+
+        let id = sqlx::query("INSERT INTO WORKOUT_SESSION (date, name, notes) VALUES (?1, ?2, ?3)")
+            .bind(session.date.to_string())
+            .bind(&session.name)
+            .bind(&session.notes)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| PersistenceError(e.to_string()))?
+            .last_insert_rowid();
+        insert_children(&mut tx, id, session)
+            .await
+            .map_err(|e| PersistenceError(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| PersistenceError(e.to_string()))?;
+        Ok(id)
+    }
+
+    #[instrument(skip(self), fields(id = session.id))]
+    async fn update_session(&self, session: &WorkoutSession) -> RepositoryResult<()> {
+        let mut conn = self
+            .pool
+            .acquire()
+            .await
+            .map_err(|e| ConnectionError(e.to_string()))?;
+        let mut tx = conn
+            .begin()
+            .await
+            .map_err(|e| PersistenceError(e.to_string()))?;
+
+        let updated = sqlx::query(
+            "UPDATE WORKOUT_SESSION SET date = ?1, name = ?2, notes = ?3 WHERE id = ?4",
+        )
+        .bind(session.date.to_string())
+        .bind(&session.name)
+        .bind(&session.notes)
+        .bind(session.id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| PersistenceError(e.to_string()))?
+        .rows_affected();
+        if updated == 0 {
+            // Dropping the transaction rolls it back
+            return Err(ItemNotFoundError);
+        }
+
+        let id = session.id.unwrap_or_default();
+        for table in ["WORKOUT_SESSION_EXERCISE", "WORKOUT_SESSION_METRIC"] {
+            sqlx::query(&format!("DELETE FROM {} WHERE session_id = ?1", table))
+                .bind(id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| PersistenceError(e.to_string()))?;
+        }
+        insert_children(&mut tx, id, session)
+            .await
+            .map_err(|e| PersistenceError(e.to_string()))?;
+
+        let dropped = sqlx::query(
+            r#"
+            SELECT exercise_id FROM WORKOUT_SET WHERE session_id = ?1 AND exercise_id NOT IN (
+                SELECT exercise_id FROM WORKOUT_SESSION_EXERCISE WHERE session_id = ?1
+            )
+            LIMIT 1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(query_error)?;
+        if let Some(row) = dropped {
+            return Err(PersistenceError(format!(
+                "exercise {} still has sets in session {}",
+                row.get::<i64, _>(0),
+                id
+            )));
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| PersistenceError(e.to_string()))
+    }
+
+    #[instrument(skip(self))]
+    async fn query_session_by_id(&self, id: i64) -> RepositoryResult<WorkoutSession> {
+        let mut conn = self
+            .pool
+            .acquire()
+            .await
+            .map_err(|e| ConnectionError(e.to_string()))?;
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM WORKOUT_SESSION WHERE id = ?1",
+            SESSION_COLUMNS
+        ))
+        .bind(id)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(query_error)?;
+        let mut session = session_row(&row)?;
+
+        session.exercises = sqlx::query(
+            r#"
+            SELECT exercise_id, notes FROM WORKOUT_SESSION_EXERCISE
+            WHERE session_id = ?1 ORDER BY position
+            "#,
+        )
+        .bind(id)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(query_error)?
+        .iter()
+        .map(|r| PerformedExercise {
+            exercise_id: r.get(0),
+            notes: r.get(1),
+        })
+        .collect();
+        session.metrics =
+            sqlx::query("SELECT name, value FROM WORKOUT_SESSION_METRIC WHERE session_id = ?1")
+                .bind(id)
+                .fetch_all(&mut *conn)
+                .await
+                .map_err(query_error)?
+                .iter()
+                .map(|r| (r.get(0), r.get(1)))
+                .collect();
+        Ok(session)
+    }
+
+    #[instrument(skip(self))]
+    async fn list_sessions(&self) -> RepositoryResult<Vec<WorkoutSession>> {
+        self.sessions(None).await
+    }
+
+    #[instrument(skip(self))]
+    async fn list_sessions_with_metric(
+        &self,
+        name: String,
+    ) -> RepositoryResult<Vec<WorkoutSession>> {
+        self.sessions(Some(&name)).await
+    }
+
+    #[instrument(skip(self))]
+    async fn delete_session(&self, id: i64) -> RepositoryResult<()> {
+        let mut conn = self
+            .pool
+            .acquire()
+            .await
+            .map_err(|e| ConnectionError(e.to_string()))?;
+        let deleted = sqlx::query("DELETE FROM WORKOUT_SESSION WHERE id = ?1")
+            .bind(id)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| DeleteError(e.to_string()))?
+            .rows_affected();
+        match deleted {
+            0 => Err(ItemNotFoundError),
+            _ => Ok(()),
+        }
+    }
+
+    #[instrument(skip(self), fields(session_id = set.session_id, exercise_id = set.exercise_id))]
+    async fn record_set(&self, set: &Set) -> RepositoryResult<Set> {
+        let mut conn = self
+            .pool
+            .acquire()
+            .await
+            .map_err(|e| ConnectionError(e.to_string()))?;
+        // Taking the write lock up front keeps a concurrent recorder from reading the same next
+        // set number
+        let mut tx = conn
+            .begin_with("BEGIN IMMEDIATE")
+            .await
+            .map_err(|e| PersistenceError(e.to_string()))?;
+
+        sqlx::query("SELECT 1 FROM WORKOUT_SESSION WHERE id = ?1")
+            .bind(set.session_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(query_error)?
+            .ok_or(ItemNotFoundError)?;
+
+        add_performed_exercise(&mut tx, set.session_id, set.exercise_id)
+            .await
+            .map_err(|e| PersistenceError(e.to_string()))?;
+
+        let row = sqlx::query(
+            r#"
+            INSERT INTO WORKOUT_SET (session_id, exercise_id, set_number, weight, reps, rpe)
+            VALUES (?1, ?2, (
+                SELECT COALESCE(MAX(set_number), 0) + 1 FROM WORKOUT_SET
+                WHERE session_id = ?1 AND exercise_id = ?2
+            ), ?3, ?4, ?5)
+            RETURNING id, set_number
+            "#,
+        )
+        .bind(set.session_id)
+        .bind(set.exercise_id)
+        .bind(set.weight.map(|w| w.to_string()))
+        .bind(set.reps)
+        .bind(set.rpe)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| PersistenceError(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| PersistenceError(e.to_string()))?;
+        Ok(Set {
+            id: Some(row.get(0)),
+            set_number: row.get(1),
+            ..set.clone()
+        })
+    }
+
+    #[instrument(skip(self), fields(id = set.id))]
+    async fn update_set(&self, set: &Set) -> RepositoryResult<()> {
+        let mut conn = self
+            .pool
+            .acquire()
+            .await
+            .map_err(|e| ConnectionError(e.to_string()))?;
+        // Like recording a set, moving one to another exercise may append to the performed
+        // exercises
+        let mut tx = conn
+            .begin_with("BEGIN IMMEDIATE")
+            .await
+            .map_err(|e| PersistenceError(e.to_string()))?;
+
+        let session_id: i64 = sqlx::query("SELECT session_id FROM WORKOUT_SET WHERE id = ?1")
+            .bind(set.id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(query_error)?
+            .ok_or(ItemNotFoundError)?
+            .get(0);
+        add_performed_exercise(&mut tx, session_id, set.exercise_id)
+            .await
+            .map_err(|e| PersistenceError(e.to_string()))?;
+
+        sqlx::query(
+            r#"
+            UPDATE WORKOUT_SET SET exercise_id = ?1, set_number = ?2, weight = ?3, reps = ?4,
+            rpe = ?5 WHERE id = ?6
+            "#,
+        )
+        .bind(set.exercise_id)
+        .bind(set.set_number)
+        .bind(set.weight.map(|w| w.to_string()))
+        .bind(set.reps)
+        .bind(set.rpe)
+        .bind(set.id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| PersistenceError(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| PersistenceError(e.to_string()))
+    }
+
+    #[instrument(skip(self))]
+    async fn delete_set(&self, id: i64) -> RepositoryResult<()> {
+        let mut conn = self
+            .pool
+            .acquire()
+            .await
+            .map_err(|e| ConnectionError(e.to_string()))?;
+        let deleted = sqlx::query("DELETE FROM WORKOUT_SET WHERE id = ?1")
+            .bind(id)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| DeleteError(e.to_string()))?
+            .rows_affected();
+        match deleted {
+            0 => Err(ItemNotFoundError),
+            _ => Ok(()),
+        }
+    }
+
+    #[instrument(skip(self))]
+    async fn list_sets(&self, session_id: i64) -> RepositoryResult<Vec<Set>> {
+        let mut conn = self
+            .pool
+            .acquire()
+            .await
+            .map_err(|e| ConnectionError(e.to_string()))?;
+        sqlx::query(&format!(
+            "SELECT {} FROM WORKOUT_SET WHERE session_id = ?1 ORDER BY id",
+            SET_COLUMNS
+        ))
+        .bind(session_id)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(query_error)?
+        .iter()
+        .map(set_row)
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{db_name, deadlift};
+    use crate::{DBType, SqliteExerciseRepository};
+    use api::RepositoryError::{ItemNotFoundError, PersistenceError};
+    use api::{
+        Exercise, ExerciseRepository, NaiveDate, PerformedExercise, Set, Weight, WorkoutSession,
+        WorkoutSessionRepository,
+    };
+    use std::collections::BTreeMap;
+    use tempfile::{tempdir, TempDir};
+    use test_log::test;
+
+    async fn repo() -> (TempDir, SqliteExerciseRepository, i64, i64) {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join(db_name());
+        let repo = SqliteExerciseRepository::new(DBType::File(file_path.as_path()))
+            .await
+            .unwrap();
+        let deadlift = repo.create(&deadlift(None)).await.unwrap();
+        let squat = repo
+            .create(&Exercise {
+                name: "Squat".to_string(),
+                ..crate::tests::deadlift(None)
+            })
+            .await
+            .unwrap();
+        (dir, repo, deadlift, squat)
+    }
+
+    fn session(date: &str, exercise_ids: &[i64]) -> WorkoutSession {
+        WorkoutSession {
+            id: None,
+            date: date.parse::<NaiveDate>().unwrap(),
+            name: Some("Heavy day".to_string()),
+            notes: None,
+            exercises: exercise_ids
+                .iter()
+                .map(|&exercise_id| PerformedExercise {
+                    exercise_id,
+                    notes: None,
+                })
+                .collect(),
+            metrics: BTreeMap::new(),
+        }
+    }
+
+    #[test(tokio::test)]
+    async fn create_and_query_ok() {
+        let (_dir, repo, deadlift, squat) = repo().await;
+        let mut expected = session("2024-05-01", &[squat, deadlift]);
+        expected.exercises[1].notes = Some("belt on".to_string());
+
+        let id = repo.create_session(&expected).await.unwrap();
+        expected.id = Some(id);
+        assert_eq!(repo.query_session_by_id(id).await.unwrap(), expected);
+    }
+
+    #[test(tokio::test)]
+    async fn create_unknown_exercise() {
+        let (_dir, repo, deadlift, _) = repo().await;
+        let result = repo
+            .create_session(&session("2024-05-01", &[deadlift, 1000]))
+            .await;
+        assert!(matches!(result.err().unwrap(), PersistenceError(_)));
+
+        // Nothing of the failed session is left behind
+        assert!(repo.list_sessions().await.unwrap().is_empty());
+    }
+
+    #[test(tokio::test)]
+    async fn query_not_found() {
+        let (_dir, repo, _, _) = repo().await;
+        let result = repo.query_session_by_id(1000).await;
+        assert!(matches!(result.err().unwrap(), ItemNotFoundError));
+    }
+
+    #[test(tokio::test)]
+    async fn update_ok() {
+        let (_dir, repo, deadlift, squat) = repo().await;
+        let id = repo
+            .create_session(&session("2024-05-01", &[squat, deadlift]))
+            .await
+            .unwrap();
+
+        let mut updated = session("2024-05-02", &[deadlift]);
+        updated.id = Some(id);
+        updated.notes = Some("moved to Thursday".to_string());
+        repo.update_session(&updated).await.unwrap();
+        assert_eq!(repo.query_session_by_id(id).await.unwrap(), updated);
+    }
+
+    #[test(tokio::test)]
+    async fn update_keeps_exercises_with_sets() {
+        let (_dir, repo, deadlift, squat) = repo().await;
+        let mut expected = session("2024-05-01", &[squat, deadlift]);
+        let id = repo.create_session(&expected).await.unwrap();
+        expected.id = Some(id);
+        repo.record_set(&set(id, squat, 0, None)).await.unwrap();
+
+        let mut updated = session("2024-05-01", &[deadlift]);
+        updated.id = Some(id);
+        let result = repo.update_session(&updated).await;
+        assert!(matches!(result.err().unwrap(), PersistenceError(_)));
+        assert_eq!(repo.query_session_by_id(id).await.unwrap(), expected);
+
+        // An exercise without sets can still be left out
+        updated.exercises = expected.exercises[..1].to_vec();
+        repo.update_session(&updated).await.unwrap();
+        assert_eq!(repo.query_session_by_id(id).await.unwrap(), updated);
+    }
+
+    #[test(tokio::test)]
+    async fn update_not_found() {
+        let (_dir, repo, deadlift, _) = repo().await;
+        let mut missing = session("2024-05-01", &[deadlift]);
+        missing.id = Some(1000);
+        let result = repo.update_session(&missing).await;
+        assert!(matches!(result.err().unwrap(), ItemNotFoundError));
+    }
+
+    #[test(tokio::test)]
+    async fn list_newest_first() {
+        let (_dir, repo, deadlift, squat) = repo().await;
+        let older = repo
+            .create_session(&session("2024-04-28", &[squat]))
+            .await
+            .unwrap();
+        let newer = repo
+            .create_session(&session("2024-05-01", &[deadlift, squat]))
+            .await
+            .unwrap();
+        let empty = repo
+            .create_session(&session("2024-04-30", &[]))
+            .await
+            .unwrap();
+
+        let sessions = repo.list_sessions().await.unwrap();
+        assert_eq!(
+            sessions.iter().map(|s| s.id.unwrap()).collect::<Vec<_>>(),
+            vec![newer, empty, older]
+        );
+        assert_eq!(sessions[0].exercises.len(), 2);
+        assert!(sessions[1].exercises.is_empty());
+        assert_eq!(sessions[2].exercises[0].exercise_id, squat);
+    }
+
+    #[test(tokio::test)]
+    async fn metrics_ok() {
+        let (_dir, repo, deadlift, _) = repo().await;
+        let mut tracked = session("2024-05-01", &[deadlift]);
+        tracked.metrics = BTreeMap::from([
+            ("box_height_cm".to_string(), 60.0),
+            ("sleep_hours".to_string(), 7.5),
+        ]);
+        let id = repo.create_session(&tracked).await.unwrap();
+        let other = repo
+            .create_session(&session("2024-05-02", &[deadlift]))
+            .await
+            .unwrap();
+        tracked.id = Some(id);
+        assert_eq!(repo.query_session_by_id(id).await.unwrap(), tracked);
+
+        let sessions = repo
+            .list_sessions_with_metric("sleep_hours".to_string())
+            .await
+            .unwrap();
+        assert_eq!(sessions, vec![tracked.clone()]);
+        assert!(repo
+            .list_sessions_with_metric("band_tension".to_string())
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(repo.list_sessions().await.unwrap()[0].id, Some(other));
+
+        // Updating replaces the metrics
+        tracked.metrics = BTreeMap::from([("sleep_hours".to_string(), 6.0)]);
+        repo.update_session(&tracked).await.unwrap();
+        assert_eq!(repo.query_session_by_id(id).await.unwrap(), tracked);
+        assert!(repo
+            .list_sessions_with_metric("box_height_cm".to_string())
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test(tokio::test)]
+    async fn delete_ok() {
+        let (_dir, repo, deadlift, _) = repo().await;
+        let id = repo
+            .create_session(&session("2024-05-01", &[deadlift]))
+            .await
+            .unwrap();
+
+        repo.delete_session(id).await.unwrap();
+        assert!(matches!(
+            repo.query_session_by_id(id).await.err().unwrap(),
+            ItemNotFoundError
+        ));
+        for table in ["WORKOUT_SESSION_EXERCISE", "WORKOUT_SESSION_METRIC"] {
+            let rows: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {}", table))
+                .fetch_one(&repo.pool)
+                .await
+                .unwrap();
+            assert_eq!(rows, 0);
+        }
+
+        let result = repo.delete_session(id).await;
+        assert!(matches!(result.err().unwrap(), ItemNotFoundError));
+    }
+
+    fn set(session_id: i64, exercise_id: i64, set_number: u32, weight: Option<Weight>) -> Set {
+        Set {
+            id: None,
+            session_id,
+            exercise_id,
+            set_number,
+            weight,
+            reps: 5,
+            rpe: None,
+        }
+    }
+
+    #[test(tokio::test)]
+    async fn sets_ok() {
+        let (_dir, repo, deadlift, squat) = repo().await;
+        let session = repo
+            .create_session(&session("2024-05-01", &[squat, deadlift]))
+            .await
+            .unwrap();
+
+        let mut first = set(session, squat, 1, Some(Weight::kg(140.0)));
+        first.rpe = Some(8.5);
+        let mut second = set(session, squat, 2, Some(Weight::lb(315.0)));
+        let mut third = set(session, deadlift, 1, None);
+        for s in [&mut first, &mut second, &mut third] {
+            *s = repo.record_set(s).await.unwrap();
+        }
+        assert_eq!(
+            repo.list_sets(session).await.unwrap(),
+            vec![first.clone(), second.clone(), third.clone()]
+        );
+
+        second.reps = 3;
+        second.rpe = Some(10.0);
+        repo.update_set(&second).await.unwrap();
+        repo.delete_set(first.id.unwrap()).await.unwrap();
+        assert_eq!(
+            repo.list_sets(session).await.unwrap(),
+            vec![second.clone(), third]
+        );
+
+        let result = repo.delete_set(first.id.unwrap()).await;
+        assert!(matches!(result.err().unwrap(), ItemNotFoundError));
+        let result = repo.update_set(&first).await;
+        assert!(matches!(result.err().unwrap(), ItemNotFoundError));
+    }
+
+    #[test(tokio::test)]
+    async fn update_set_number_taken() {
+        let (_dir, repo, deadlift, _) = repo().await;
+        let session = repo
+            .create_session(&session("2024-05-01", &[deadlift]))
+            .await
+            .unwrap();
+        let first = repo
+            .record_set(&set(session, deadlift, 0, None))
+            .await
+            .unwrap();
+        let mut second = repo
+            .record_set(&set(session, deadlift, 0, None))
+            .await
+            .unwrap();
+
+        second.set_number = first.set_number;
+        let result = repo.update_set(&second).await;
+        assert!(matches!(result.err().unwrap(), PersistenceError(_)));
+    }
+
+    #[test(tokio::test)]
+    async fn record_set_numbers_and_adds_exercise() {
+        let (_dir, repo, deadlift, squat) = repo().await;
+        let session = repo
+            .create_session(&session("2024-05-01", &[squat]))
+            .await
+            .unwrap();
+
+        let first = repo
+            .record_set(&set(session, deadlift, 7, Some(Weight::kg(180.0))))
+            .await
+            .unwrap();
+        assert_eq!(first.set_number, 1);
+        let second = repo
+            .record_set(&set(session, deadlift, 1, Some(Weight::kg(190.0))))
+            .await
+            .unwrap();
+        assert_eq!(second.set_number, 2);
+        assert_eq!(
+            repo.list_sets(session).await.unwrap(),
+            vec![first.clone(), second]
+        );
+
+        let exercises = repo.query_session_by_id(session).await.unwrap().exercises;
+        assert_eq!(
+            exercises.iter().map(|e| e.exercise_id).collect::<Vec<_>>(),
+            vec![squat, deadlift]
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn record_set_concurrently() {
+        let (_dir, repo, deadlift, _) = repo().await;
+        let session = repo
+            .create_session(&session("2024-05-01", &[]))
+            .await
+            .unwrap();
+
+        let recorded = set(session, deadlift, 0, None);
+        let (a, b, c) = tokio::join!(
+            repo.record_set(&recorded),
+            repo.record_set(&recorded),
+            repo.record_set(&recorded)
+        );
+        let mut numbers = vec![
+            a.unwrap().set_number,
+            b.unwrap().set_number,
+            c.unwrap().set_number,
+        ];
+        numbers.sort();
+        assert_eq!(numbers, vec![1, 2, 3]);
+        assert_eq!(
+            repo.query_session_by_id(session)
+                .await
+                .unwrap()
+                .exercises
+                .len(),
+            1
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn record_set_rejected() {
+        let (_dir, repo, deadlift, _) = repo().await;
+        let session = repo
+            .create_session(&session("2024-05-01", &[]))
+            .await
+            .unwrap();
+
+        let result = repo.record_set(&set(1000, deadlift, 0, None)).await;
+        assert!(matches!(result.err().unwrap(), ItemNotFoundError));
+
+        // Neither the set nor the exercise of a rejected set is left behind
+        let result = repo.record_set(&set(session, 1000, 0, None)).await;
+        assert!(matches!(result.err().unwrap(), PersistenceError(_)));
+        assert!(repo.list_sets(session).await.unwrap().is_empty());
+        assert!(repo
+            .query_session_by_id(session)
+            .await
+            .unwrap()
+            .exercises
+            .is_empty());
+    }
+
+    #[test(tokio::test)]
+    async fn update_set_adds_exercise() {
+        let (_dir, repo, deadlift, squat) = repo().await;
+        let session = repo
+            .create_session(&session("2024-05-01", &[squat]))
+            .await
+            .unwrap();
+        let mut recorded = repo
+            .record_set(&set(session, squat, 0, Some(Weight::kg(180.0))))
+            .await
+            .unwrap();
+
+        // The set was meant for the deadlift
+        recorded.exercise_id = deadlift;
+        repo.update_set(&recorded).await.unwrap();
+        assert_eq!(
+            repo.list_sets(session).await.unwrap(),
+            vec![recorded.clone()]
+        );
+        let exercises = repo.query_session_by_id(session).await.unwrap().exercises;
+        assert_eq!(
+            exercises.iter().map(|e| e.exercise_id).collect::<Vec<_>>(),
+            vec![squat, deadlift]
+        );
+
+        // Neither is changed when the exercise does not exist
+        let mut unknown = recorded.clone();
+        unknown.exercise_id = 1000;
+        let result = repo.update_set(&unknown).await;
+        assert!(matches!(result.err().unwrap(), PersistenceError(_)));
+        assert_eq!(repo.list_sets(session).await.unwrap(), vec![recorded]);
+        assert_eq!(
+            repo.query_session_by_id(session)
+                .await
+                .unwrap()
+                .exercises
+                .len(),
+            2
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn delete_session_deletes_sets() {
+        let (_dir, repo, deadlift, _) = repo().await;
+        let session = repo
+            .create_session(&session("2024-05-01", &[deadlift]))
+            .await
+            .unwrap();
+        repo.record_set(&set(session, deadlift, 0, None))
+            .await
+            .unwrap();
+
+        repo.delete_session(session).await.unwrap();
+        assert!(repo.list_sets(session).await.unwrap().is_empty());
+    }
+}
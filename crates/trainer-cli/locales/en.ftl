@@ -0,0 +1,35 @@
+# Messages of the trainer CLI.  The English messages match the Display of the errors.
+
+cli-missing-database = no database configured for profile '{ $profile }', pass --database or run `trainer config set database <path>`
+cli-unknown-profile = profile '{ $profile }' does not exist in the configuration file
+cli-config-value-not-set = { $key } is not set for profile '{ $profile }'
+cli-config = configuration error: { $detail }
+cli-exercise-not-found = exercise '{ $name }' was not found
+cli-no-exercise-selected = no exercise was selected, pass its name
+cli-no-terminal = this command needs an interactive terminal
+cli-notify-failed = notify command '{ $command }' failed: { $detail }
+cli-exercise = exercise operation failed: { $reason }
+cli-repository = repository operation failed: { $reason }
+cli-integrity-check-failed = the database integrity check found { $count } problems, restore it from a backup
+cli-import = unable to read import file { $detail }
+cli-transfer = transfer failed: { $reason }
+cli-format = unable to format output: { $detail }
+cli-io = unable to write output: { $detail }
+
+error-exercise-not-found = ExerciseNotFoundError
+error-lookup-failed = LookupError
+error-save-failed = SaveFailed
+error-delete-failed = DeleteFailed
+error-restore-failed = RestoreFailed
+error-duplicate-name = DuplicateNameError
+error-unknown = UnknownError
+
+error-persistence-failed = PersistenceError: { $detail }
+error-connection-failed = ConnectionError: { $detail }
+error-query-failed = QueryError: { $detail }
+error-repository-delete-failed = DeleteError: { $detail }
+error-item-not-found = ItemNotFoundError
+error-duplicate-id = DuplicateIdError
+error-repository-duplicate-name = DuplicateNameError: { $name }
+error-schema-mismatch = SchemaMismatchError: the database is at schema version { $database } but this build only supports up to { $supported }, upgrade trainer to use it
+error-repository-unknown = Unknown: { $detail }
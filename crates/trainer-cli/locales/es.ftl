@@ -0,0 +1,35 @@
+# Mensajes de la CLI de trainer.
+
+cli-missing-database = no hay una base de datos configurada para el perfil '{ $profile }', usa --database o ejecuta `trainer config set database <ruta>`
+cli-unknown-profile = el perfil '{ $profile }' no existe en el archivo de configuración
+cli-config-value-not-set = { $key } no está definido para el perfil '{ $profile }'
+cli-config = error de configuración: { $detail }
+cli-exercise-not-found = no se encontró el ejercicio '{ $name }'
+cli-no-exercise-selected = no se seleccionó ningún ejercicio, indica su nombre
+cli-no-terminal = este comando necesita una terminal interactiva
+cli-notify-failed = el comando de aviso '{ $command }' falló: { $detail }
+cli-exercise = la operación sobre el ejercicio falló: { $reason }
+cli-repository = la operación sobre la base de datos falló: { $reason }
+cli-integrity-check-failed = la comprobación de integridad de la base de datos encontró { $count } problemas, restáurala desde una copia de seguridad
+cli-import = no se pudo leer el archivo de importación { $detail }
+cli-transfer = la transferencia falló: { $reason }
+cli-format = no se pudo dar formato a la salida: { $detail }
+cli-io = no se pudo escribir la salida: { $detail }
+
+error-exercise-not-found = ejercicio no encontrado
+error-lookup-failed = la búsqueda falló
+error-save-failed = no se pudo guardar
+error-delete-failed = no se pudo eliminar
+error-restore-failed = no se pudo restaurar
+error-duplicate-name = ya existe un ejercicio con ese nombre
+error-unknown = error desconocido
+
+error-persistence-failed = error al guardar: { $detail }
+error-connection-failed = error de conexión: { $detail }
+error-query-failed = error de consulta: { $detail }
+error-repository-delete-failed = error al eliminar: { $detail }
+error-item-not-found = elemento no encontrado
+error-duplicate-id = identificador duplicado
+error-repository-duplicate-name = ya existe un ejercicio llamado '{ $name }'
+error-schema-mismatch = la base de datos está en la versión de esquema { $database } pero esta versión solo admite hasta la { $supported }, actualiza trainer para usarla
+error-repository-unknown = desconocido: { $detail }
@@ -0,0 +1,139 @@
+use crate::commands::config::ConfigCommand;
+use crate::commands::db::DbCommand;
+use crate::commands::exercise::ExerciseCommand;
+use crate::commands::init::InitArgs;
+use crate::commands::max::MaxArgs;
+use crate::commands::timer::TimerArgs;
+use crate::commands::transfer::{ExportArgs, ImportArgs};
+use crate::completion::Shell;
+use crate::output::OutputFormat;
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+#[derive(Parser, Debug)]
+#[command(
+    name = "trainer",
+    version,
+    about = "Track your strength training from the terminal"
+)]
+pub struct Cli {
+    /// Configuration file [default: ~/.config/trainer/config.toml]
+    #[arg(long, global = true)]
+    pub config: Option<PathBuf>,
+
+    /// Profile of the configuration file to use [default: the default_profile of the file]
+    #[arg(long, short = 'p', global = true)]
+    pub profile: Option<String>,
+
+    /// Path to the sqlite database file, overriding the profile
+    #[arg(long, global = true)]
+    pub database: Option<PathBuf>,
+
+    /// Output format for list and show commands
+    #[arg(long, short = 'o', global = true, value_enum, default_value_t)]
+    pub output: OutputFormat,
+
+    /// Print nothing when a command fails, leaving only the exit status
+    #[arg(long, short = 'q', global = true, conflicts_with = "json_errors")]
+    pub quiet: bool,
+
+    /// Write errors as JSON whatever the output format
+    #[arg(long, global = true)]
+    pub json_errors: bool,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+impl Cli {
+    /// The format errors are written in, or `None` when they are not written at all.
+    pub fn error_format(&self) -> Option<OutputFormat> {
+        match (self.quiet, self.json_errors) {
+            (true, _) => None,
+            (false, true) => Some(OutputFormat::Json),
+            (false, false) => Some(self.output),
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Set up the database and configuration file for a first workout
+    Init(InitArgs),
+
+    /// Manage exercises
+    #[command(subcommand)]
+    Exercise(ExerciseCommand),
+
+    /// Export exercises
+    Export(ExportArgs),
+
+    /// Import exercises from a CSV file
+    Import(ImportArgs),
+
+    /// Browse and search exercises in an interactive terminal dashboard
+    Tui,
+
+    /// Estimate the 1RM of an exercise from a set and print a table of percentages of it
+    Max(MaxArgs),
+
+    /// Count down a rest between sets, ringing the bell when it is over
+    Timer(TimerArgs),
+
+    /// Print the JSON Schema of a model, for validating data outside of trainer
+    Schema {
+        /// Name of the model
+        #[arg(value_parser = api::schema::SCHEMA_NAMES)]
+        name: String,
+    },
+
+    /// Maintain the database file
+    #[command(subcommand)]
+    Db(DbCommand),
+
+    /// View and change configuration profiles
+    #[command(subcommand)]
+    Config(ConfigCommand),
+
+    /// Print a shell completion script, e.g. `source <(trainer completions bash)`
+    Completions {
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+
+    /// Print the man page, or write one page per subcommand into a directory
+    Man {
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn verify_cli() {
+        Cli::command().debug_assert()
+    }
+
+    #[test]
+    fn error_format() {
+        let parse = |args: &[&str]| Cli::try_parse_from(args).map(|cli| cli.error_format());
+        assert_eq!(
+            parse(&["trainer", "tui"]).unwrap(),
+            Some(OutputFormat::Table)
+        );
+        assert_eq!(
+            parse(&["trainer", "tui", "-o", "yaml"]).unwrap(),
+            Some(OutputFormat::Yaml)
+        );
+        assert_eq!(
+            parse(&["trainer", "tui", "-o", "yaml", "--json-errors"]).unwrap(),
+            Some(OutputFormat::Json)
+        );
+        assert_eq!(parse(&["trainer", "-q", "tui"]).unwrap(), None);
+        assert!(parse(&["trainer", "tui", "--quiet", "--json-errors"]).is_err());
+    }
+}
@@ -0,0 +1,319 @@
+use crate::config::{Config, ConfigKey, Profile};
+use crate::error::{CliError, CliResult};
+use crate::output::{write_list, write_one, CsvRow, OutputFormat};
+use clap::{Subcommand, ValueEnum};
+use serde::Serialize;
+use std::io::Write;
+use std::path::Path;
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommand {
+    /// Show every value set in the profile
+    Show,
+
+    /// Print a single value of the profile
+    Get {
+        #[arg(value_enum)]
+        key: ConfigKey,
+    },
+
+    /// Change a value of the profile, creating the profile if needed
+    Set {
+        #[arg(value_enum)]
+        key: ConfigKey,
+        value: String,
+    },
+
+    /// Remove a value from the profile
+    Unset {
+        #[arg(value_enum)]
+        key: ConfigKey,
+    },
+
+    /// Make a profile the default
+    Use {
+        /// Name of the profile
+        profile: String,
+    },
+
+    /// List the profiles in the configuration file
+    Profiles,
+
+    /// Print the location of the configuration file
+    Path,
+}
+
+#[derive(Serialize)]
+struct ProfileSummary {
+    name: String,
+    default: bool,
+}
+
+impl CsvRow for ProfileSummary {
+    const COLUMNS: &'static [&'static str] = &["name", "default"];
+}
+
+/// Runs a config command against the file at `path`.  `profile` is the profile requested on the
+/// command line, falling back to the default profile of the file.
+pub fn run<W: Write>(
+    command: ConfigCommand,
+    path: Option<&Path>,
+    profile: Option<&str>,
+    format: OutputFormat,
+    out: &mut W,
+) -> CliResult<()> {
+    let path = path.ok_or_else(|| {
+        CliError::Config("unable to locate the configuration file, pass --config".to_string())
+    })?;
+    let mut config = Config::load(path)?;
+    let name = profile
+        .unwrap_or_else(|| config.active_profile_name())
+        .to_string();
+
+    match command {
+        ConfigCommand::Show => {
+            let values = match config.profiles.get(&name) {
+                Some(p) => p.clone(),
+                None if profile.is_none() => Profile::default(),
+                None => return Err(CliError::UnknownProfile(name)),
+            };
+            write_one(out, format, &values, write_profile)?;
+        }
+        ConfigCommand::Get { key } => {
+            let value = config.profiles.get(&name).and_then(|p| p.get(key));
+            match value {
+                Some(v) => writeln!(out, "{}", v)?,
+                None => return Err(CliError::ConfigValueNotSet(key_name(key), name)),
+            }
+        }
+        ConfigCommand::Set { key, value } => {
+            config
+                .profiles
+                .entry(name.clone())
+                .or_default()
+                .set(key, &value)?;
+            config.save(path)?;
+            writeln!(out, "Set {} for profile '{}'", key_name(key), name)?;
+        }
+        ConfigCommand::Unset { key } => {
+            if let Some(p) = config.profiles.get_mut(&name) {
+                p.unset(key);
+                config.save(path)?;
+            }
+            writeln!(out, "Unset {} for profile '{}'", key_name(key), name)?;
+        }
+        ConfigCommand::Use { profile } => {
+            config.profiles.entry(profile.clone()).or_default();
+            config.default_profile = Some(profile.clone());
+            config.save(path)?;
+            writeln!(out, "Default profile is now '{}'", profile)?;
+        }
+        ConfigCommand::Profiles => {
+            let active = config.active_profile_name();
+            let profiles: Vec<ProfileSummary> = config
+                .profiles
+                .keys()
+                .map(|p| ProfileSummary {
+                    name: p.clone(),
+                    default: p == active,
+                })
+                .collect();
+            write_list(out, format, &profiles, write_profiles)?;
+        }
+        ConfigCommand::Path => writeln!(out, "{}", path.display())?,
+    }
+    Ok(())
+}
+
+fn key_name(key: ConfigKey) -> String {
+    // ConfigKey has no skipped variants, so every key has a possible value
+    key.to_possible_value().unwrap().get_name().to_string()
+}
+
+fn write_profile<W: Write>(out: &mut W, profile: &Profile) -> CliResult<()> {
+    for key in ConfigKey::value_variants() {
+        if let Some(value) = profile.get(*key) {
+            writeln!(out, "{} = {}", key_name(*key), value)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_profiles<W: Write>(out: &mut W, profiles: &[ProfileSummary]) -> CliResult<()> {
+    for p in profiles {
+        let marker = if p.default { "*" } else { " " };
+        writeln!(out, "{} {}", marker, p.name)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Units;
+    use std::path::PathBuf;
+    use tempfile::{tempdir, TempDir};
+
+    fn config_file() -> (TempDir, PathBuf) {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("trainer").join("config.toml");
+        (dir, path)
+    }
+
+    fn output(command: ConfigCommand, path: &Path, profile: Option<&str>) -> String {
+        let mut out = Vec::new();
+        run(command, Some(path), profile, OutputFormat::Table, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn set_get_unset() {
+        let (_dir, path) = config_file();
+
+        let set = output(
+            ConfigCommand::Set {
+                key: ConfigKey::Database,
+                value: "/data/trainer.db3".to_string(),
+            },
+            &path,
+            None,
+        );
+        assert_eq!(set, "Set database for profile 'default'\n");
+
+        let got = output(
+            ConfigCommand::Get {
+                key: ConfigKey::Database,
+            },
+            &path,
+            None,
+        );
+        assert_eq!(got, "/data/trainer.db3\n");
+
+        output(
+            ConfigCommand::Unset {
+                key: ConfigKey::Database,
+            },
+            &path,
+            None,
+        );
+        let mut out = Vec::new();
+        let result = run(
+            ConfigCommand::Get {
+                key: ConfigKey::Database,
+            },
+            Some(&path),
+            None,
+            OutputFormat::Table,
+            &mut out,
+        );
+        assert!(matches!(
+            result.err().unwrap(),
+            CliError::ConfigValueNotSet(key, profile) if key == "database" && profile == "default"
+        ))
+    }
+
+    #[test]
+    fn set_named_profile_and_show() {
+        let (_dir, path) = config_file();
+        output(
+            ConfigCommand::Set {
+                key: ConfigKey::RemoteUrl,
+                value: "https://trainer.example.com".to_string(),
+            },
+            &path,
+            Some("gym"),
+        );
+        output(
+            ConfigCommand::Set {
+                key: ConfigKey::Units,
+                value: "lb".to_string(),
+            },
+            &path,
+            Some("gym"),
+        );
+
+        let config = Config::load(&path).unwrap();
+        assert_eq!(config.profiles["gym"].units, Some(Units::Lb));
+
+        let shown = output(ConfigCommand::Show, &path, Some("gym"));
+        assert_eq!(
+            shown,
+            "remote-url = https://trainer.example.com\nunits = lb\n"
+        );
+    }
+
+    #[test]
+    fn show_unknown_profile() {
+        let (_dir, path) = config_file();
+        let mut out = Vec::new();
+        let result = run(
+            ConfigCommand::Show,
+            Some(&path),
+            Some("gym"),
+            OutputFormat::Table,
+            &mut out,
+        );
+        assert!(matches!(result.err().unwrap(), CliError::UnknownProfile(_)))
+    }
+
+    #[test]
+    fn use_and_list_profiles() {
+        let (_dir, path) = config_file();
+        output(
+            ConfigCommand::Set {
+                key: ConfigKey::Database,
+                value: "/data/home.db3".to_string(),
+            },
+            &path,
+            Some("home"),
+        );
+        let used = output(
+            ConfigCommand::Use {
+                profile: "gym".to_string(),
+            },
+            &path,
+            None,
+        );
+        assert_eq!(used, "Default profile is now 'gym'\n");
+
+        let profiles = output(ConfigCommand::Profiles, &path, None);
+        assert_eq!(profiles, "* gym\n  home\n");
+
+        let mut out = Vec::new();
+        run(
+            ConfigCommand::Profiles,
+            Some(&path),
+            None,
+            OutputFormat::Json,
+            &mut out,
+        )
+        .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(json[0]["name"], "gym");
+        assert_eq!(json[0]["default"], true);
+        crate::output::assert_csv_columns(&ProfileSummary {
+            name: "gym".to_string(),
+            default: true,
+        });
+    }
+
+    #[test]
+    fn path() {
+        let (_dir, path) = config_file();
+        let printed = output(ConfigCommand::Path, &path, None);
+        assert_eq!(printed, format!("{}\n", path.display()));
+    }
+
+    #[test]
+    fn no_config_path() {
+        let mut out = Vec::new();
+        let result = run(
+            ConfigCommand::Path,
+            None,
+            None,
+            OutputFormat::Table,
+            &mut out,
+        );
+        assert!(matches!(result.err().unwrap(), CliError::Config(_)))
+    }
+}
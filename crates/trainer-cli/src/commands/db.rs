@@ -0,0 +1,196 @@
+use crate::error::{CliError, CliResult};
+use crate::output::{write_csv, write_one, OutputFormat};
+use clap::Subcommand;
+use serde::Serialize;
+use sqlite::{DatabaseStats, MaintenanceReport, SqliteExerciseRepository};
+use std::io::Write;
+
+#[derive(Subcommand, Debug)]
+pub enum DbCommand {
+    /// Check the integrity of the database, then analyze and vacuum it
+    Maintain,
+
+    /// Show the size of the database and its write-ahead log and the rows of each table.  CSV
+    /// output only holds the rows, one line per table
+    Stats,
+}
+
+pub async fn run<W: Write>(
+    repo: &SqliteExerciseRepository,
+    command: DbCommand,
+    format: OutputFormat,
+    out: &mut W,
+) -> CliResult<()> {
+    match command {
+        DbCommand::Maintain => {
+            let report = repo.maintain().await?;
+            match format {
+                OutputFormat::Csv => write_csv(out, &[MaintenanceRow::from(&report)])?,
+                _ => write_one(out, format, &report, write_report)?,
+            }
+            if !report.integrity_errors.is_empty() {
+                return Err(CliError::IntegrityCheckFailed(
+                    report.integrity_errors.len(),
+                ));
+            }
+        }
+        DbCommand::Stats => {
+            let stats = repo.stats().await?;
+            match format {
+                OutputFormat::Csv => write_csv(out, &stats.tables)?,
+                _ => write_one(out, format, &stats, write_stats)?,
+            }
+        }
+    }
+    Ok(())
+}
+
+/// A [`MaintenanceReport`] as a CSV row, with one problem per line of `integrity_errors`.
+#[derive(Serialize)]
+struct MaintenanceRow {
+    integrity_errors: String,
+    optimized: bool,
+    size_before: u64,
+    size_after: u64,
+}
+
+impl From<&MaintenanceReport> for MaintenanceRow {
+    fn from(report: &MaintenanceReport) -> Self {
+        Self {
+            integrity_errors: report.integrity_errors.join("\n"),
+            optimized: report.optimized,
+            size_before: report.size_before,
+            size_after: report.size_after,
+        }
+    }
+}
+
+fn write_stats<W: Write>(out: &mut W, stats: &DatabaseStats) -> CliResult<()> {
+    writeln!(out, "Size:       {} bytes", stats.size)?;
+    writeln!(out, "WAL size:   {} bytes", stats.wal_size)?;
+    writeln!(out, "Rows:")?;
+    for table in &stats.tables {
+        writeln!(out, "  {:<20} {}", table.name, table.rows)?;
+    }
+    Ok(())
+}
+
+fn write_report<W: Write>(out: &mut W, report: &MaintenanceReport) -> CliResult<()> {
+    if report.integrity_errors.is_empty() {
+        writeln!(out, "Integrity:  ok")?;
+    } else {
+        writeln!(
+            out,
+            "Integrity:  {} problems",
+            report.integrity_errors.len()
+        )?;
+        for error in &report.integrity_errors {
+            writeln!(out, "  {}", error)?;
+        }
+    }
+    match report.optimized {
+        true => writeln!(out, "Optimized:  analyzed and vacuumed")?,
+        false => writeln!(out, "Optimized:  skipped")?,
+    }
+    writeln!(
+        out,
+        "Size:       {} -> {} bytes",
+        report.size_before, report.size_after
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlite::DBType;
+    use tempfile::tempdir;
+    use test_log::test;
+
+    #[test(tokio::test)]
+    async fn maintain_ok() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("trainer.db3");
+        let repo = SqliteExerciseRepository::new(DBType::File(path.as_path()))
+            .await
+            .unwrap();
+
+        let mut out = Vec::new();
+        run(&repo, DbCommand::Maintain, OutputFormat::Table, &mut out)
+            .await
+            .unwrap();
+        let report = String::from_utf8(out).unwrap();
+        assert!(report.starts_with("Integrity:  ok\nOptimized:  analyzed and vacuumed\n"));
+
+        let mut out = Vec::new();
+        run(&repo, DbCommand::Maintain, OutputFormat::Json, &mut out)
+            .await
+            .unwrap();
+        let report: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(report["integrity_errors"], serde_json::json!([]));
+        assert_eq!(report["optimized"], true);
+
+        let mut out = Vec::new();
+        run(&repo, DbCommand::Maintain, OutputFormat::Csv, &mut out)
+            .await
+            .unwrap();
+        let report = String::from_utf8(out).unwrap();
+        assert!(report.starts_with("integrity_errors,optimized,size_before,size_after\n,true,"));
+    }
+
+    #[test]
+    fn maintenance_row_joins_errors() {
+        let report = MaintenanceReport {
+            integrity_errors: vec![
+                "page 3 is never used".to_string(),
+                "row 1 missing".to_string(),
+            ],
+            optimized: false,
+            size_before: 8192,
+            size_after: 8192,
+        };
+        let mut out = Vec::new();
+        write_csv(&mut out, &[MaintenanceRow::from(&report)]).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "integrity_errors,optimized,size_before,size_after\n\"page 3 is never used\nrow 1 missing\",false,8192,8192\n"
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn stats_ok() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("trainer.db3");
+        let repo = SqliteExerciseRepository::new(DBType::File(path.as_path()))
+            .await
+            .unwrap();
+
+        let mut out = Vec::new();
+        run(&repo, DbCommand::Stats, OutputFormat::Table, &mut out)
+            .await
+            .unwrap();
+        let stats = String::from_utf8(out).unwrap();
+        assert!(stats.contains("WAL size:   0 bytes\n"));
+        assert!(stats.contains("  EXERCISE             0\n"));
+
+        let mut out = Vec::new();
+        run(&repo, DbCommand::Stats, OutputFormat::Json, &mut out)
+            .await
+            .unwrap();
+        let stats: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(stats["size"], repo.size().await.unwrap());
+        assert!(stats["tables"]
+            .as_array()
+            .unwrap()
+            .contains(&serde_json::json!({"name": "METADATA", "rows": 1})));
+
+        let mut out = Vec::new();
+        run(&repo, DbCommand::Stats, OutputFormat::Csv, &mut out)
+            .await
+            .unwrap();
+        let stats = String::from_utf8(out).unwrap();
+        assert!(stats.starts_with("name,rows\n"));
+        assert!(stats.contains("\nEXERCISE,0\n"));
+        assert!(stats.contains("\nMETADATA,1\n"));
+    }
+}
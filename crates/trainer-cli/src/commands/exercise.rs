@@ -0,0 +1,673 @@
+use crate::completion::complete_exercise_name;
+use crate::error::{CliError, CliResult};
+use crate::output::{write_list, write_one, CsvRow, OutputFormat};
+use crate::picker::pick_exercise;
+use api::{Exercise, ExerciseError, ExerciseManagement, ExerciseType, Weight};
+use clap::{Subcommand, ValueEnum};
+use clap_complete::engine::ArgValueCompleter;
+use std::io::Write;
+
+#[derive(Subcommand, Debug)]
+pub enum ExerciseCommand {
+    /// Add a new exercise
+    Add {
+        /// Unique name of the exercise
+        name: String,
+
+        /// The kind of equipment used
+        #[arg(long = "type", short = 't', value_enum)]
+        exercise_type: ExerciseTypeArg,
+
+        /// Optional free-form description
+        #[arg(long, short = 'd')]
+        description: Option<String>,
+
+        /// Weight of the empty bar, e.g. 20kg or 45lb
+        #[arg(long)]
+        bar_weight: Option<Weight>,
+
+        /// Smallest load change the available plates allow, e.g. 2.5kg or 5lb
+        #[arg(long)]
+        increment: Option<Weight>,
+    },
+
+    /// List all exercises
+    List,
+
+    /// Show the details of a single exercise
+    Show {
+        /// Name of the exercise (case-insensitive), picked interactively when omitted
+        #[arg(add = ArgValueCompleter::new(complete_exercise_name))]
+        name: Option<String>,
+    },
+
+    /// Change the name, type, description or loading rules of an exercise
+    Edit {
+        /// Current name of the exercise (case-insensitive), picked interactively when omitted
+        #[arg(add = ArgValueCompleter::new(complete_exercise_name))]
+        name: Option<String>,
+
+        /// New name for the exercise
+        #[arg(long)]
+        rename: Option<String>,
+
+        /// New exercise type
+        #[arg(long = "type", short = 't', value_enum)]
+        exercise_type: Option<ExerciseTypeArg>,
+
+        /// New description
+        #[arg(long, short = 'd')]
+        description: Option<String>,
+
+        /// New bar weight, e.g. 20kg or 45lb
+        #[arg(long)]
+        bar_weight: Option<Weight>,
+
+        /// New load increment, e.g. 2.5kg or 5lb
+        #[arg(long)]
+        increment: Option<Weight>,
+    },
+
+    /// List the deleted exercises, which can still be restored
+    Trash,
+
+    /// Restore a deleted exercise
+    Restore {
+        /// Name of the deleted exercise (case-insensitive)
+        name: String,
+    },
+
+    /// Remember another name for an exercise, used to match the names found in imports.  An alias
+    /// that already exists is moved to this exercise
+    Alias {
+        /// Name of the exercise (case-insensitive)
+        #[arg(add = ArgValueCompleter::new(complete_exercise_name))]
+        name: String,
+
+        /// The other name, e.g. the one another app uses
+        alias: String,
+    },
+
+    /// Delete an exercise
+    Delete {
+        /// Name of the exercise (case-insensitive), picked interactively when omitted
+        #[arg(add = ArgValueCompleter::new(complete_exercise_name))]
+        name: Option<String>,
+    },
+}
+
+/// Command line representation of [`ExerciseType`]
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum ExerciseTypeArg {
+    #[value(alias = "bb")]
+    Barbell,
+    #[value(alias = "kb")]
+    Kettlebell,
+    #[value(alias = "bw")]
+    Bodyweight,
+}
+
+impl From<ExerciseTypeArg> for ExerciseType {
+    fn from(value: ExerciseTypeArg) -> Self {
+        match value {
+            ExerciseTypeArg::Barbell => ExerciseType::Barbell,
+            ExerciseTypeArg::Kettlebell => ExerciseType::KettleBell,
+            ExerciseTypeArg::Bodyweight => ExerciseType::BodyWeight,
+        }
+    }
+}
+
+pub async fn run<M, W>(
+    mgr: &M,
+    command: ExerciseCommand,
+    format: OutputFormat,
+    out: &mut W,
+) -> CliResult<()>
+where
+    M: ExerciseManagement + Sync,
+    W: Write,
+{
+    match command {
+        ExerciseCommand::Add {
+            name,
+            exercise_type,
+            description,
+            bar_weight,
+            increment,
+        } => {
+            let mut exercise = Exercise {
+                id: None,
+                name,
+                description,
+                exercise_type: exercise_type.into(),
+                bar_weight,
+                load_increment: increment,
+            };
+            mgr.save(&mut exercise).await?;
+            writeln!(out, "Added exercise '{}'", exercise.name)?;
+        }
+        ExerciseCommand::List => {
+            let exercises = mgr.list().await?;
+            write_list(out, format, &exercises, write_table)?;
+        }
+        ExerciseCommand::Show { name } => {
+            let exercise = get(mgr, pick_exercise(mgr, name).await?).await?;
+            write_one(out, format, &exercise, write_details)?;
+        }
+        ExerciseCommand::Edit {
+            name,
+            rename,
+            exercise_type,
+            description,
+            bar_weight,
+            increment,
+        } => {
+            let mut exercise = get(mgr, pick_exercise(mgr, name).await?).await?;
+            if let Some(new_name) = rename {
+                exercise.name = new_name;
+            }
+            if let Some(et) = exercise_type {
+                exercise.exercise_type = et.into();
+            }
+            if description.is_some() {
+                exercise.description = description;
+            }
+            if bar_weight.is_some() {
+                exercise.bar_weight = bar_weight;
+            }
+            if increment.is_some() {
+                exercise.load_increment = increment;
+            }
+            mgr.save(&mut exercise).await?;
+            writeln!(out, "Updated exercise '{}'", exercise.name)?;
+        }
+        ExerciseCommand::Trash => {
+            let exercises = mgr.deleted().await?;
+            write_list(out, format, &exercises, write_table)?;
+        }
+        ExerciseCommand::Restore { name } => match mgr.restore(name.clone()).await {
+            Ok(_) => writeln!(out, "Restored exercise '{}'", name)?,
+            Err(ExerciseError::ExerciseNotFoundError) => {
+                return Err(CliError::ExerciseNotFound(name))
+            }
+            Err(e) => return Err(e.into()),
+        },
+        ExerciseCommand::Alias { name, alias } => {
+            match mgr.add_alias(name.clone(), alias.clone()).await {
+                Ok(_) => writeln!(out, "Added alias '{}' for '{}'", alias, name)?,
+                Err(ExerciseError::ExerciseNotFoundError) => {
+                    return Err(CliError::ExerciseNotFound(name))
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+        ExerciseCommand::Delete { name } => {
+            let name = pick_exercise(mgr, name).await?;
+            match mgr.delete(name.clone()).await {
+                Ok(_) => writeln!(out, "Deleted exercise '{}'", name)?,
+                Err(ExerciseError::ExerciseNotFoundError) => {
+                    return Err(CliError::ExerciseNotFound(name))
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+    Ok(())
+}
+
+pub(crate) async fn get<M: ExerciseManagement + Sync>(
+    mgr: &M,
+    name: String,
+) -> CliResult<Exercise> {
+    match mgr.get_by_name(name.clone()).await {
+        Ok(exercise) => Ok(exercise),
+        Err(ExerciseError::ExerciseNotFoundError) => Err(CliError::ExerciseNotFound(name)),
+        Err(e) => Err(e.into()),
+    }
+}
+
+impl CsvRow for Exercise {
+    const COLUMNS: &'static [&'static str] = &[
+        "id",
+        "name",
+        "description",
+        "exercise_type",
+        "bar_weight",
+        "load_increment",
+    ];
+}
+
+fn write_table<W: Write>(out: &mut W, exercises: &[Exercise]) -> CliResult<()> {
+    let name_width = exercises
+        .iter()
+        .map(|e| e.name.len())
+        .chain(std::iter::once("NAME".len()))
+        .max()
+        .unwrap_or_default();
+    let type_width = exercises
+        .iter()
+        .map(|e| e.exercise_type.to_string().len())
+        .chain(std::iter::once("TYPE".len()))
+        .max()
+        .unwrap_or_default();
+
+    writeln!(
+        out,
+        "{:<name_width$}  {:<type_width$}  DESCRIPTION",
+        "NAME", "TYPE"
+    )?;
+    for e in exercises {
+        let row = format!(
+            "{:<name_width$}  {:<type_width$}  {}",
+            e.name,
+            e.exercise_type.to_string(),
+            e.description.as_deref().unwrap_or_default()
+        );
+        writeln!(out, "{}", row.trim_end())?;
+    }
+    Ok(())
+}
+
+fn write_details<W: Write>(out: &mut W, exercise: &Exercise) -> CliResult<()> {
+    writeln!(out, "Name:        {}", exercise.name)?;
+    writeln!(out, "Type:        {}", exercise.exercise_type)?;
+    writeln!(
+        out,
+        "Description: {}",
+        exercise.description.as_deref().unwrap_or_default()
+    )?;
+    if let Some(weight) = exercise.bar_weight {
+        writeln!(out, "Bar weight:  {}", weight)?;
+    }
+    if let Some(weight) = exercise.load_increment {
+        writeln!(out, "Increment:   {}", weight)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use api::{ExerciseManager, TrainerErrorCode};
+    use rand::distributions::Alphanumeric;
+    use rand::{thread_rng, Rng};
+    use sqlite::{DBType, SqliteExerciseRepository};
+    use tempfile::{tempdir, TempDir};
+    use test_log::test;
+
+    fn db_name() -> String {
+        let rand_string: String = thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(10)
+            .map(char::from)
+            .collect();
+
+        format!("testdb-{}.db3", rand_string)
+    }
+
+    async fn repo() -> (TempDir, SqliteExerciseRepository) {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join(db_name());
+        let repo = SqliteExerciseRepository::new(DBType::File(file_path.as_path()))
+            .await
+            .unwrap();
+        (dir, repo)
+    }
+
+    fn add_deadlift() -> ExerciseCommand {
+        ExerciseCommand::Add {
+            name: "Deadlift".to_string(),
+            exercise_type: ExerciseTypeArg::Barbell,
+            description: Some("Pull from the floor".to_string()),
+            bar_weight: None,
+            increment: None,
+        }
+    }
+
+    async fn output<M: ExerciseManagement + Sync>(mgr: &M, command: ExerciseCommand) -> String {
+        let mut out = Vec::new();
+        run(mgr, command, OutputFormat::Table, &mut out)
+            .await
+            .unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn exercise_type_arg_into_exercise_type() {
+        assert_eq!(
+            ExerciseType::from(ExerciseTypeArg::Barbell),
+            ExerciseType::Barbell
+        );
+        assert_eq!(
+            ExerciseType::from(ExerciseTypeArg::Kettlebell),
+            ExerciseType::KettleBell
+        );
+        assert_eq!(
+            ExerciseType::from(ExerciseTypeArg::Bodyweight),
+            ExerciseType::BodyWeight
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn add_and_show_ok() {
+        let (_dir, repo) = repo().await;
+        let mgr = ExerciseManager::new(&repo).unwrap();
+
+        let added = output(&mgr, add_deadlift()).await;
+        assert_eq!(added, "Added exercise 'Deadlift'\n");
+
+        let shown = output(
+            &mgr,
+            ExerciseCommand::Show {
+                name: Some("deadlift".to_string()),
+            },
+        )
+        .await;
+        assert!(shown.contains("Name:        Deadlift"));
+        assert!(shown.contains("Type:        Barbell"));
+        assert!(shown.contains("Description: Pull from the floor"));
+        assert!(!shown.contains("Bar weight:"));
+    }
+
+    #[test(tokio::test)]
+    async fn list_ok() {
+        let (_dir, repo) = repo().await;
+        let mgr = ExerciseManager::new(&repo).unwrap();
+        output(&mgr, add_deadlift()).await;
+        output(
+            &mgr,
+            ExerciseCommand::Add {
+                name: "Swing".to_string(),
+                exercise_type: ExerciseTypeArg::Kettlebell,
+                description: None,
+                bar_weight: None,
+                increment: None,
+            },
+        )
+        .await;
+
+        let listed = output(&mgr, ExerciseCommand::List).await;
+        let lines: Vec<&str> = listed.lines().collect();
+        assert_eq!(3, lines.len());
+        assert!(lines[0].starts_with("NAME"));
+        assert!(lines[1].starts_with("Deadlift  Barbell"));
+        assert!(lines[2].starts_with("Swing     KettleBell"));
+    }
+
+    #[test(tokio::test)]
+    async fn list_json_ok() {
+        let (_dir, repo) = repo().await;
+        let mgr = ExerciseManager::new(&repo).unwrap();
+        output(&mgr, add_deadlift()).await;
+
+        let mut out = Vec::new();
+        run(&mgr, ExerciseCommand::List, OutputFormat::Json, &mut out)
+            .await
+            .unwrap();
+        let exercises: Vec<Exercise> = serde_json::from_slice(&out).unwrap();
+        assert_eq!(1, exercises.len());
+        assert_eq!(exercises[0].name, "Deadlift");
+        assert_eq!(exercises[0].exercise_type, ExerciseType::Barbell);
+    }
+
+    #[test(tokio::test)]
+    async fn show_csv_ok() {
+        let (_dir, repo) = repo().await;
+        let mgr = ExerciseManager::new(&repo).unwrap();
+        output(&mgr, add_deadlift()).await;
+
+        let mut out = Vec::new();
+        run(
+            &mgr,
+            ExerciseCommand::Show {
+                name: Some("Deadlift".to_string()),
+            },
+            OutputFormat::Csv,
+            &mut out,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "id,name,description,exercise_type,bar_weight,load_increment\n1,Deadlift,Pull from the floor,barbell,,\n"
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn list_csv_empty() {
+        let (_dir, repo) = repo().await;
+        let mgr = ExerciseManager::new(&repo).unwrap();
+
+        let mut out = Vec::new();
+        run(&mgr, ExerciseCommand::List, OutputFormat::Csv, &mut out)
+            .await
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "id,name,description,exercise_type,bar_weight,load_increment\n"
+        );
+        crate::output::assert_csv_columns(&Exercise {
+            id: Some(1),
+            name: "Deadlift".to_string(),
+            description: None,
+            exercise_type: ExerciseType::Barbell,
+            bar_weight: None,
+            load_increment: None,
+        });
+    }
+
+    #[test(tokio::test)]
+    async fn edit_ok() {
+        let (_dir, repo) = repo().await;
+        let mgr = ExerciseManager::new(&repo).unwrap();
+        output(&mgr, add_deadlift()).await;
+
+        let edited = output(
+            &mgr,
+            ExerciseCommand::Edit {
+                name: Some("Deadlift".to_string()),
+                rename: Some("Sumo Deadlift".to_string()),
+                exercise_type: None,
+                description: Some("Wide stance".to_string()),
+                bar_weight: None,
+                increment: None,
+            },
+        )
+        .await;
+        assert_eq!(edited, "Updated exercise 'Sumo Deadlift'\n");
+
+        let exercise = mgr.get_by_name("Sumo Deadlift".to_string()).await.unwrap();
+        assert_eq!(exercise.exercise_type, ExerciseType::Barbell);
+        assert_eq!(exercise.description, Some("Wide stance".to_string()));
+    }
+
+    #[test(tokio::test)]
+    async fn edit_loading_rules_ok() {
+        let (_dir, repo) = repo().await;
+        let mgr = ExerciseManager::new(&repo).unwrap();
+        output(&mgr, add_deadlift()).await;
+
+        output(
+            &mgr,
+            ExerciseCommand::Edit {
+                name: Some("Deadlift".to_string()),
+                rename: None,
+                exercise_type: None,
+                description: None,
+                bar_weight: Some(Weight::kg(20.0)),
+                increment: Some(Weight::lb(5.0)),
+            },
+        )
+        .await;
+
+        let shown = output(
+            &mgr,
+            ExerciseCommand::Show {
+                name: Some("Deadlift".to_string()),
+            },
+        )
+        .await;
+        assert!(shown.contains("Description: Pull from the floor"));
+        assert!(shown.contains("Bar weight:  20kg"));
+        assert!(shown.contains("Increment:   5lb"));
+    }
+
+    #[test(tokio::test)]
+    async fn alias_ok() {
+        let (_dir, repo) = repo().await;
+        let mgr = ExerciseManager::new(&repo).unwrap();
+        output(&mgr, add_deadlift()).await;
+
+        let added = output(
+            &mgr,
+            ExerciseCommand::Alias {
+                name: "deadlift".to_string(),
+                alias: "Deadlift (Barbell)".to_string(),
+            },
+        )
+        .await;
+        assert_eq!(added, "Added alias 'Deadlift (Barbell)' for 'deadlift'\n");
+        let aliases = mgr.aliases().await.unwrap();
+        assert_eq!(aliases.len(), 1);
+        assert_eq!(aliases[0].alias, "Deadlift (Barbell)");
+
+        let mut out = Vec::new();
+        let result = run(
+            &mgr,
+            ExerciseCommand::Alias {
+                name: "Squat".to_string(),
+                alias: "Back Squat".to_string(),
+            },
+            OutputFormat::Table,
+            &mut out,
+        )
+        .await;
+        assert!(matches!(
+            result.err().unwrap(),
+            CliError::ExerciseNotFound(name) if name == "Squat"
+        ))
+    }
+
+    #[test(tokio::test)]
+    async fn delete_ok() {
+        let (_dir, repo) = repo().await;
+        let mgr = ExerciseManager::new(&repo).unwrap();
+        output(&mgr, add_deadlift()).await;
+
+        let deleted = output(
+            &mgr,
+            ExerciseCommand::Delete {
+                name: Some("Deadlift".to_string()),
+            },
+        )
+        .await;
+        assert_eq!(deleted, "Deleted exercise 'Deadlift'\n");
+        assert!(mgr.list().await.unwrap().is_empty());
+    }
+
+    #[test(tokio::test)]
+    async fn trash_and_restore_ok() {
+        let (_dir, repo) = repo().await;
+        let mgr = ExerciseManager::new(&repo).unwrap();
+        output(&mgr, add_deadlift()).await;
+        output(
+            &mgr,
+            ExerciseCommand::Delete {
+                name: Some("Deadlift".to_string()),
+            },
+        )
+        .await;
+
+        let trash = output(&mgr, ExerciseCommand::Trash).await;
+        let lines: Vec<&str> = trash.lines().collect();
+        assert_eq!(2, lines.len());
+        assert!(lines[1].starts_with("Deadlift  Barbell"));
+
+        let restored = output(
+            &mgr,
+            ExerciseCommand::Restore {
+                name: "deadlift".to_string(),
+            },
+        )
+        .await;
+        assert_eq!(restored, "Restored exercise 'deadlift'\n");
+        assert_eq!(mgr.list().await.unwrap().len(), 1);
+        assert_eq!(
+            output(&mgr, ExerciseCommand::Trash).await.lines().count(),
+            1
+        );
+
+        let mut out = Vec::new();
+        let result = run(
+            &mgr,
+            ExerciseCommand::Restore {
+                name: "Deadlift".to_string(),
+            },
+            OutputFormat::Table,
+            &mut out,
+        )
+        .await;
+        assert!(matches!(
+            result.err().unwrap(),
+            CliError::ExerciseNotFound(_)
+        ))
+    }
+
+    #[test(tokio::test)]
+    async fn show_not_found() {
+        let (_dir, repo) = repo().await;
+        let mgr = ExerciseManager::new(&repo).unwrap();
+        let mut out = Vec::new();
+        let result = run(
+            &mgr,
+            ExerciseCommand::Show {
+                name: Some("Deadlift".to_string()),
+            },
+            OutputFormat::Table,
+            &mut out,
+        )
+        .await;
+        assert!(matches!(
+            result.err().unwrap(),
+            CliError::ExerciseNotFound(name) if name == "Deadlift"
+        ))
+    }
+
+    #[test(tokio::test)]
+    async fn delete_not_found() {
+        let (_dir, repo) = repo().await;
+        let mgr = ExerciseManager::new(&repo).unwrap();
+        let mut out = Vec::new();
+        let result = run(
+            &mgr,
+            ExerciseCommand::Delete {
+                name: Some("Deadlift".to_string()),
+            },
+            OutputFormat::Table,
+            &mut out,
+        )
+        .await;
+        assert!(matches!(
+            result.err().unwrap(),
+            CliError::ExerciseNotFound(_)
+        ))
+    }
+
+    #[test(tokio::test)]
+    async fn add_duplicate_failed() {
+        let (_dir, repo) = repo().await;
+        let mgr = ExerciseManager::new(&repo).unwrap();
+        output(&mgr, add_deadlift()).await;
+
+        let mut out = Vec::new();
+        let err = run(&mgr, add_deadlift(), OutputFormat::Table, &mut out)
+            .await
+            .err()
+            .unwrap();
+        assert!(matches!(
+            err,
+            CliError::Exercise(ExerciseError::DuplicateNameError)
+        ));
+        assert_eq!(err.code(), TrainerErrorCode::DuplicateName);
+    }
+}
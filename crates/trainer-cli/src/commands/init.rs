@@ -0,0 +1,246 @@
+use crate::config::{Config, Profile};
+use crate::error::CliResult;
+use crate::output::{write_one, OutputFormat};
+use crate::{open_repository, Settings};
+use api::{ExerciseManagement, ExerciseManager};
+use clap::Args;
+use serde::Serialize;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use transfer::library::standard_library;
+use transfer::matcher::ExerciseMatcher;
+use transfer::plan::{apply, Action, ImportPlan};
+
+#[derive(Args, Debug)]
+pub struct InitArgs {
+    /// Add the standard library of barbell, kettlebell and bodyweight exercises
+    #[arg(long)]
+    pub seed: bool,
+}
+
+/// What [`init`] did, so running it again on a set up install is harmless and says so.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct InitReport {
+    pub profile: String,
+    pub database: PathBuf,
+    pub database_created: bool,
+    pub config: Option<PathBuf>,
+    pub config_written: bool,
+    pub seeded: usize,
+}
+
+/// Sets up `profile` (or the default profile) for a first workout: creates the data directory and
+/// the migrated database, adds the exercises of the standard library that don't exist yet when
+/// `seed` is set, and writes the profile to the configuration file at `config_path` unless it is
+/// already there.  Existing profiles and exercises are never changed.
+pub async fn init(
+    config_path: Option<&Path>,
+    profile: Option<&str>,
+    database: Option<PathBuf>,
+    seed: bool,
+) -> CliResult<InitReport> {
+    let mut config = match config_path {
+        Some(path) => Config::load(path)?,
+        None => Config::default(),
+    };
+    let name = profile
+        .unwrap_or_else(|| config.active_profile_name())
+        .to_string();
+    let new_profile = !config.profiles.contains_key(&name);
+    let settings = match new_profile {
+        true => Settings::from_config(&Config::default(), None, database)?,
+        false => Settings::from_config(&config, Some(&name), database)?,
+    };
+
+    let path = settings.database()?;
+    let database_created = !path.exists();
+    let repo = open_repository(&settings).await?;
+
+    let mut seeded = 0;
+    if seed {
+        let mgr = ExerciseManager::new(&repo)?;
+        let existing = mgr.list().await?;
+        let aliases = mgr.aliases().await?;
+        // Deleted exercises are skipped by the plan, they were removed on purpose
+        let deleted = mgr.deleted().await?;
+        let mut plan = ImportPlan::new(
+            standard_library(),
+            &ExerciseMatcher::new(&existing, &aliases),
+            &deleted,
+        );
+        plan.changes.retain(|c| c.action == Action::Create);
+        let summary = apply(&mgr, &plan).await?;
+        if let Some(failed) = summary.failed.into_iter().next() {
+            return Err(failed.error.into());
+        }
+        seeded = summary.created;
+    }
+
+    let config_written = new_profile && config_path.is_some();
+    if let (true, Some(config_path)) = (config_written, config_path) {
+        config.profiles.insert(
+            name.clone(),
+            Profile {
+                database: Some(path.clone()),
+                ..Default::default()
+            },
+        );
+        if config.default_profile.is_none() {
+            config.default_profile = Some(name.clone());
+        }
+        config.save(config_path)?;
+    }
+
+    Ok(InitReport {
+        profile: name,
+        database: path,
+        database_created,
+        config: config_path.map(Path::to_path_buf),
+        config_written,
+        seeded,
+    })
+}
+
+pub async fn run<W: Write>(
+    config_path: Option<&Path>,
+    profile: Option<&str>,
+    database: Option<PathBuf>,
+    args: InitArgs,
+    format: OutputFormat,
+    out: &mut W,
+) -> CliResult<()> {
+    let report = init(config_path, profile, database, args.seed).await?;
+    write_one(out, format, &report, write_report)
+}
+
+fn write_report<W: Write>(out: &mut W, report: &InitReport) -> CliResult<()> {
+    let status = |done: bool, verb: &str| match done {
+        true => verb.to_string(),
+        false => "unchanged".to_string(),
+    };
+    writeln!(out, "Profile:    {}", report.profile)?;
+    writeln!(
+        out,
+        "Database:   {} ({})",
+        report.database.display(),
+        status(report.database_created, "created")
+    )?;
+    match &report.config {
+        Some(path) => writeln!(
+            out,
+            "Config:     {} ({})",
+            path.display(),
+            status(report.config_written, "written")
+        )?,
+        None => writeln!(out, "Config:     not written, pass --config")?,
+    }
+    writeln!(out, "Seeded:     {} exercises", report.seeded)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlite::{DBType, SqliteExerciseRepository};
+    use std::fs;
+    use tempfile::tempdir;
+    use test_log::test;
+
+    #[test(tokio::test)]
+    async fn init_then_again() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("config").join("config.toml");
+        let database = dir.path().join("data").join("trainer.db3");
+
+        let report = init(Some(&config_path), None, Some(database.clone()), true)
+            .await
+            .unwrap();
+        let library = standard_library().exercises.len();
+        assert_eq!(
+            report,
+            InitReport {
+                profile: "default".to_string(),
+                database: database.clone(),
+                database_created: true,
+                config: Some(config_path.clone()),
+                config_written: true,
+                seeded: library,
+            }
+        );
+        let config = Config::load(&config_path).unwrap();
+        assert_eq!(config.default_profile.as_deref(), Some("default"));
+        assert_eq!(
+            config.profiles["default"].database.as_deref(),
+            Some(database.as_path())
+        );
+
+        // The second run finds everything in place, reading the database from the config
+        let written = fs::read_to_string(&config_path).unwrap();
+        let report = init(Some(&config_path), None, None, true).await.unwrap();
+        assert!(!report.database_created);
+        assert!(!report.config_written);
+        assert_eq!(report.database, database);
+        assert_eq!(report.seeded, 0);
+        assert_eq!(fs::read_to_string(&config_path).unwrap(), written);
+
+        let repo = SqliteExerciseRepository::new(DBType::File(&database))
+            .await
+            .unwrap();
+        let mgr = ExerciseManager::new(&repo).unwrap();
+        assert_eq!(mgr.list().await.unwrap().len(), library);
+
+        // Seeding again leaves deleted exercises in the trash
+        mgr.delete("Deadlift".to_string()).await.unwrap();
+        let report = init(Some(&config_path), None, None, true).await.unwrap();
+        assert_eq!(report.seeded, 0);
+        assert_eq!(mgr.list().await.unwrap().len(), library - 1);
+    }
+
+    #[test(tokio::test)]
+    async fn init_adds_profile() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("config.toml");
+        fs::write(&config_path, "default_profile = \"home\"\n").unwrap();
+        let database = dir.path().join("gym.db3");
+
+        let report = init(
+            Some(&config_path),
+            Some("gym"),
+            Some(database.clone()),
+            false,
+        )
+        .await
+        .unwrap();
+        assert!(report.database_created);
+        assert!(report.config_written);
+        assert_eq!(report.seeded, 0);
+
+        let config = Config::load(&config_path).unwrap();
+        assert_eq!(config.default_profile.as_deref(), Some("home"));
+        assert_eq!(
+            config.profiles["gym"].database.as_deref(),
+            Some(database.as_path())
+        );
+    }
+
+    #[test]
+    fn write_report_table() {
+        let report = InitReport {
+            profile: "default".to_string(),
+            database: PathBuf::from("/data/trainer.db3"),
+            database_created: true,
+            config: None,
+            config_written: false,
+            seeded: 3,
+        };
+        let mut out = Vec::new();
+        write_report(&mut out, &report).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "Profile:    default\n\
+             Database:   /data/trainer.db3 (created)\n\
+             Config:     not written, pass --config\n\
+             Seeded:     3 exercises\n"
+        );
+    }
+}
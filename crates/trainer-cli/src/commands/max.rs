@@ -0,0 +1,298 @@
+use crate::commands::exercise::get;
+use crate::completion::complete_exercise_name;
+use crate::config::Units;
+use crate::error::CliResult;
+use crate::output::{write_list, CsvRow, OutputFormat};
+use crate::picker::pick_exercise;
+use api::{Exercise, ExerciseManagement, OneRepMaxFormula, ParseWeightError, Weight};
+use clap::{Args, ValueEnum};
+use clap_complete::engine::ArgValueCompleter;
+use serde::Serialize;
+use std::io::Write;
+
+/// Percentages of the 1RM in the table, heaviest first.
+const PERCENTAGES: [u32; 11] = [100, 95, 90, 85, 80, 75, 70, 65, 60, 55, 50];
+
+#[derive(Args, Debug)]
+pub struct MaxArgs {
+    /// Name of the exercise, whose bar weight and load increment round the loads.  Picked
+    /// interactively when omitted
+    #[arg(add = ArgValueCompleter::new(complete_exercise_name))]
+    pub name: Option<String>,
+
+    /// Weight of the set, e.g. 180kg or 405lb, or just 180 in the units of the profile
+    #[arg(long)]
+    pub weight: WeightArg,
+
+    /// Reps of the set
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..=36))]
+    pub reps: u32,
+
+    /// Formula estimating the 1RM
+    #[arg(long, value_enum, default_value_t)]
+    pub formula: FormulaArg,
+}
+
+/// A weight on the command line, where a bare number is in the units of the profile.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WeightArg {
+    Weight(Weight),
+    Bare(f64),
+}
+
+impl WeightArg {
+    pub fn in_units(self, units: Units) -> Weight {
+        match self {
+            WeightArg::Weight(weight) => weight,
+            WeightArg::Bare(value) => Weight {
+                value,
+                unit: units.into(),
+            },
+        }
+    }
+}
+
+impl std::str::FromStr for WeightArg {
+    type Err = ParseWeightError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().parse::<f64>() {
+            Ok(value) if value.is_finite() && value >= 0.0 => Ok(WeightArg::Bare(value)),
+            Ok(_) => Err(ParseWeightError(s.to_string())),
+            Err(_) => s.parse().map(WeightArg::Weight),
+        }
+    }
+}
+
+/// Command line representation of [`OneRepMaxFormula`]
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
+pub enum FormulaArg {
+    #[default]
+    Epley,
+    Brzycki,
+    Lombardi,
+}
+
+impl From<FormulaArg> for OneRepMaxFormula {
+    fn from(value: FormulaArg) -> Self {
+        match value {
+            FormulaArg::Epley => OneRepMaxFormula::Epley,
+            FormulaArg::Brzycki => OneRepMaxFormula::Brzycki,
+            FormulaArg::Lombardi => OneRepMaxFormula::Lombardi,
+        }
+    }
+}
+
+/// A row of the percentage table.  `load` is `weight` rounded to what can be put on the bar.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Percentage {
+    pub percent: u32,
+    pub weight: Weight,
+    pub load: Weight,
+}
+
+impl CsvRow for Percentage {
+    const COLUMNS: &'static [&'static str] = &["percent", "weight", "load"];
+}
+
+/// Estimates the 1RM in the units of the profile, whatever unit the set was given in.  The loads
+/// stay in the unit of the exercise's increment, as that is the unit of the plates.
+pub async fn run<M, W>(
+    mgr: &M,
+    args: MaxArgs,
+    units: Units,
+    format: OutputFormat,
+    out: &mut W,
+) -> CliResult<()>
+where
+    M: ExerciseManagement + Sync,
+    W: Write,
+{
+    let exercise = get(mgr, pick_exercise(mgr, args.name).await?).await?;
+    let formula = OneRepMaxFormula::from(args.formula);
+    let weight = args.weight.in_units(units).to_unit(units.into());
+    // The reps are limited to the range every formula supports
+    let max = formula.estimate(weight, args.reps).unwrap();
+    let rows = percentages(&exercise, max);
+
+    write_list(out, format, &rows, |out, rows| {
+        writeln!(
+            out,
+            "Estimated 1RM of {} ({}): {}",
+            exercise.name, formula, rows[0].weight
+        )?;
+        writeln!(out)?;
+        writeln!(out, "PERCENT  WEIGHT     LOAD")?;
+        for row in rows {
+            let percent = format!("{}%", row.percent);
+            writeln!(
+                out,
+                "{:<7}  {:<9}  {}",
+                percent,
+                row.weight.to_string(),
+                row.load
+            )?;
+        }
+        Ok(())
+    })
+}
+
+fn percentages(exercise: &Exercise, max: Weight) -> Vec<Percentage> {
+    PERCENTAGES
+        .iter()
+        .map(|percent| {
+            let weight = Weight {
+                value: (max.value * *percent as f64 / 10.0).round() / 10.0,
+                unit: max.unit,
+            };
+            Percentage {
+                percent: *percent,
+                weight,
+                load: exercise.loadable(weight),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use api::{ExerciseManager, ExerciseType};
+    use sqlite::{DBType, SqliteExerciseRepository};
+    use tempfile::tempdir;
+    use test_log::test;
+
+    fn deadlift() -> Exercise {
+        Exercise {
+            id: None,
+            name: "Deadlift".to_string(),
+            description: None,
+            exercise_type: ExerciseType::Barbell,
+            bar_weight: Some(Weight::kg(20.0)),
+            load_increment: Some(Weight::kg(2.5)),
+        }
+    }
+
+    #[test]
+    fn percentages_ok() {
+        let rows = percentages(&deadlift(), Weight::kg(210.0));
+        assert_eq!(rows.len(), PERCENTAGES.len());
+        assert_eq!(
+            rows[0],
+            Percentage {
+                percent: 100,
+                weight: Weight::kg(210.0),
+                load: Weight::kg(210.0),
+            }
+        );
+        assert_eq!(rows[2].weight, Weight::kg(189.0));
+        assert_eq!(rows[2].load, Weight::kg(190.0));
+        assert_eq!(rows[10].load, Weight::kg(105.0));
+        crate::output::assert_csv_columns(&rows[0]);
+    }
+
+    #[test(tokio::test)]
+    async fn run_ok() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("trainer.db3");
+        let repo = SqliteExerciseRepository::new(DBType::File(path.as_path()))
+            .await
+            .unwrap();
+        let mgr = ExerciseManager::new(&repo).unwrap();
+        mgr.save(&mut deadlift()).await.unwrap();
+
+        let args = |formula| MaxArgs {
+            name: Some("deadlift".to_string()),
+            weight: WeightArg::Weight(Weight::kg(180.0)),
+            reps: 5,
+            formula,
+        };
+        let mut out = Vec::new();
+        run(
+            &mgr,
+            args(FormulaArg::Epley),
+            Units::Kg,
+            OutputFormat::Table,
+            &mut out,
+        )
+        .await
+        .unwrap();
+        let table = String::from_utf8(out).unwrap();
+        assert!(table.starts_with("Estimated 1RM of Deadlift (Epley): 210kg\n"));
+        assert!(table.contains("\n90%      189kg      190kg\n"));
+
+        let mut out = Vec::new();
+        run(
+            &mgr,
+            args(FormulaArg::Brzycki),
+            Units::Kg,
+            OutputFormat::Json,
+            &mut out,
+        )
+        .await
+        .unwrap();
+        let rows: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(rows[0]["weight"], "202.5kg");
+        assert_eq!(rows[1]["load"], "192.5kg");
+    }
+
+    #[test(tokio::test)]
+    async fn run_in_profile_units() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("trainer.db3");
+        let repo = SqliteExerciseRepository::new(DBType::File(path.as_path()))
+            .await
+            .unwrap();
+        let mgr = ExerciseManager::new(&repo).unwrap();
+        mgr.save(&mut deadlift()).await.unwrap();
+
+        let args = |weight| MaxArgs {
+            name: Some("deadlift".to_string()),
+            weight,
+            reps: 5,
+            formula: FormulaArg::Epley,
+        };
+
+        // A bare weight is in the units of the profile
+        let mut out = Vec::new();
+        run(
+            &mgr,
+            args("405".parse().unwrap()),
+            Units::Lb,
+            OutputFormat::Json,
+            &mut out,
+        )
+        .await
+        .unwrap();
+        let rows: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(rows[0]["weight"], "472.5lb");
+        assert_eq!(rows[0]["load"], "215kg");
+
+        // A weight with a unit is converted to them
+        let mut out = Vec::new();
+        run(
+            &mgr,
+            args(WeightArg::Weight(Weight::kg(180.0))),
+            Units::Lb,
+            OutputFormat::Json,
+            &mut out,
+        )
+        .await
+        .unwrap();
+        let rows: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(rows[0]["weight"], "463lb");
+        assert_eq!(rows[0]["load"], "210kg");
+    }
+
+    #[test]
+    fn weight_arg_parse() {
+        assert_eq!("180".parse(), Ok(WeightArg::Bare(180.0)));
+        assert_eq!("405lb".parse(), Ok(WeightArg::Weight(Weight::lb(405.0))));
+        assert!("-5".parse::<WeightArg>().is_err());
+        assert!("heavy".parse::<WeightArg>().is_err());
+        assert_eq!(
+            WeightArg::Bare(100.0).in_units(Units::Lb),
+            Weight::lb(100.0)
+        );
+    }
+}
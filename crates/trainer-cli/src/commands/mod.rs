@@ -0,0 +1,8 @@
+pub mod config;
+pub mod db;
+pub mod exercise;
+pub mod init;
+pub mod max;
+pub mod schema;
+pub mod timer;
+pub mod transfer;
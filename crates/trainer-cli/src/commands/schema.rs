@@ -0,0 +1,31 @@
+use crate::error::{CliError, CliResult};
+use std::io::Write;
+
+pub fn run<W: Write>(name: &str, out: &mut W) -> CliResult<()> {
+    let schema = api::schema::schema(name)
+        .ok_or_else(|| CliError::Format(format!("no schema for '{}'", name)))?;
+    let json =
+        serde_json::to_string_pretty(&schema).map_err(|e| CliError::Format(e.to_string()))?;
+    writeln!(out, "{}", json)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_ok() {
+        let mut out = Vec::new();
+        run("exercise", &mut out).unwrap();
+        let schema: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(schema["title"], "Exercise");
+    }
+
+    #[test]
+    fn run_unknown_name() {
+        let mut out = Vec::new();
+        let result = run("workout", &mut out);
+        assert!(matches!(result.err().unwrap(), CliError::Format(_)))
+    }
+}
@@ -0,0 +1,116 @@
+use crate::error::{CliError, CliResult};
+use clap::Args;
+use std::future::Future;
+use std::io::Write;
+use std::time::Duration;
+use tokio::process::Command;
+
+#[derive(Args, Debug)]
+pub struct TimerArgs {
+    /// Length of the rest in seconds
+    #[arg(long, default_value_t = 180)]
+    pub rest: u64,
+
+    /// Shell command run when the rest is over, e.g. `notify-send "Rest over"`.  It is run with
+    /// `sh -c`, or `cmd /C` on Windows
+    #[arg(long)]
+    pub notify: Option<String>,
+
+    /// Don't ring the terminal bell when the rest is over
+    #[arg(long)]
+    pub no_bell: bool,
+}
+
+pub async fn run<W: Write>(args: TimerArgs, out: &mut W) -> CliResult<()> {
+    countdown(args.rest, out, tokio::time::sleep).await?;
+    match args.no_bell {
+        true => writeln!(out, "Rest over")?,
+        false => writeln!(out, "Rest over\x07")?,
+    }
+    out.flush()?;
+
+    if let Some(command) = args.notify {
+        let status = shell(&command)
+            .status()
+            .await
+            .map_err(|e| CliError::NotifyFailed(command.clone(), e.to_string()))?;
+        if !status.success() {
+            return Err(CliError::NotifyFailed(command, status.to_string()));
+        }
+    }
+    Ok(())
+}
+
+/// The platform's shell running `command`.
+fn shell(command: &str) -> Command {
+    #[cfg(windows)]
+    let (program, flag) = ("cmd", "/C");
+    #[cfg(not(windows))]
+    let (program, flag) = ("sh", "-c");
+
+    let mut shell = Command::new(program);
+    shell.arg(flag).arg(command);
+    shell
+}
+
+/// Counts down from `rest` seconds on a single line, waiting a second between updates with
+/// `sleep` so tests don't have to.
+async fn countdown<W, F, Fut>(rest: u64, out: &mut W, mut sleep: F) -> CliResult<()>
+where
+    W: Write,
+    F: FnMut(Duration) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    for remaining in (1..=rest).rev() {
+        write!(out, "\rRest {}:{:02}", remaining / 60, remaining % 60)?;
+        out.flush()?;
+        sleep(Duration::from_secs(1)).await;
+    }
+    write!(out, "\rRest 0:00\n")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    #[test(tokio::test)]
+    async fn countdown_ok() {
+        let mut out = Vec::new();
+        let mut slept = Duration::ZERO;
+        countdown(61, &mut out, |d| {
+            slept += d;
+            async {}
+        })
+        .await
+        .unwrap();
+
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("\rRest 1:01\rRest 1:00\rRest 0:59"));
+        assert!(out.ends_with("\rRest 0:01\rRest 0:00\n"));
+        assert_eq!(slept, Duration::from_secs(61));
+    }
+
+    #[test(tokio::test)]
+    async fn run_notify() {
+        let mut out = Vec::new();
+        let args = TimerArgs {
+            rest: 0,
+            notify: Some("true".to_string()),
+            no_bell: true,
+        };
+        run(args, &mut out).await.unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "\rRest 0:00\nRest over\n");
+
+        let args = TimerArgs {
+            rest: 0,
+            notify: Some("exit 3".to_string()),
+            no_bell: false,
+        };
+        let result = run(args, &mut Vec::new()).await;
+        let err = result.err().unwrap();
+        assert!(matches!(err, CliError::NotifyFailed(ref c, _) if c == "exit 3"));
+        assert_eq!(err.code(), api::TrainerErrorCode::NotifyFailed);
+    }
+}
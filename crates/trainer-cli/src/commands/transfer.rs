@@ -0,0 +1,700 @@
+use crate::commands::exercise::ExerciseTypeArg;
+use crate::error::{CliError, CliResult};
+use api::ExerciseManagement;
+use clap::{Args, ValueEnum};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use transfer::exercise_csv::{read_exercises, write_exercises, ColumnMapping, ParsedExercises};
+use transfer::fitnotes::FitNotesImporter;
+use transfer::hevy::HevyImporter;
+use transfer::matcher::ExerciseMatcher;
+use transfer::names::NameMapping;
+use transfer::plan::{apply, Action, ImportPlan, PlannedChange, SkipReason};
+use transfer::wger::read_catalog;
+use transfer::workout::WorkoutImporter;
+
+#[derive(Args, Debug)]
+pub struct ExportArgs {
+    /// File format of the export
+    #[arg(long, value_enum, default_value_t)]
+    pub format: ExportFormat,
+
+    /// Write to this file instead of standard output
+    #[arg(long, short = 'f')]
+    pub file: Option<PathBuf>,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+}
+
+#[derive(Args, Debug)]
+pub struct ImportArgs {
+    /// CSV file to import
+    pub file: PathBuf,
+
+    /// App that produced the file
+    #[arg(long, value_enum, default_value_t)]
+    pub from: ImportSource,
+
+    /// CSV file with `source,name[,exercise_type]` rows translating exercise names of the app
+    #[arg(long)]
+    pub names: Option<PathBuf>,
+
+    /// Use a different column for a field of a trainer file, e.g. `--map name=Exercise`
+    #[arg(long = "map", value_name = "FIELD=COLUMN", value_parser = parse_mapping)]
+    pub mappings: Vec<(ImportField, String)>,
+
+    /// Exercise type for rows without one
+    #[arg(long, value_enum)]
+    pub default_type: Option<ExerciseTypeArg>,
+
+    /// Report what would be created and updated without changing anything
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
+pub enum ImportSource {
+    /// A file written by `trainer export`, or any CSV of exercises
+    #[default]
+    Trainer,
+    /// The workout export of Hevy
+    Hevy,
+    /// The spreadsheet export of FitNotes
+    Fitnotes,
+    /// A page of the wger exercise catalog (JSON from the `exerciseinfo` endpoint)
+    Wger,
+}
+
+/// The exercise fields that can be mapped to a column of the import file.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum ImportField {
+    Name,
+    Type,
+    Description,
+}
+
+fn parse_mapping(value: &str) -> Result<(ImportField, String), String> {
+    let (field, column) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected FIELD=COLUMN, found '{}'", value))?;
+    let field = ImportField::from_str(field.trim(), true).map_err(|_| {
+        format!(
+            "unknown field '{}', expected name, type or description",
+            field
+        )
+    })?;
+    Ok((field, column.trim().to_string()))
+}
+
+impl ImportArgs {
+    fn column_mapping(&self) -> ColumnMapping {
+        let mut mapping = ColumnMapping {
+            default_type: self.default_type.map(|t| t.into()),
+            ..Default::default()
+        };
+        for (field, column) in &self.mappings {
+            match field {
+                ImportField::Name => mapping.name = column.clone(),
+                ImportField::Type => mapping.exercise_type = column.clone(),
+                ImportField::Description => mapping.description = column.clone(),
+            }
+        }
+        mapping
+    }
+}
+
+pub async fn export<M, W>(mgr: &M, args: ExportArgs, out: &mut W) -> CliResult<()>
+where
+    M: ExerciseManagement + Sync,
+    W: Write,
+{
+    let exercises = mgr.list().await?;
+    match (args.format, args.file) {
+        (ExportFormat::Csv, Some(path)) => {
+            write_exercises(BufWriter::new(File::create(&path)?), &exercises)?;
+            writeln!(
+                out,
+                "Exported {} exercises to {}",
+                exercises.len(),
+                path.display()
+            )?;
+        }
+        (ExportFormat::Csv, None) => write_exercises(out, &exercises)?,
+    }
+    Ok(())
+}
+
+pub async fn import<M, W>(mgr: &M, args: ImportArgs, out: &mut W) -> CliResult<()>
+where
+    M: ExerciseManagement + Sync,
+    W: Write,
+{
+    let parsed = match args.from {
+        ImportSource::Trainer => read_exercises(open(&args.file)?, &args.column_mapping())?,
+        ImportSource::Hevy => read_workouts(&HevyImporter, &args, out)?,
+        ImportSource::Fitnotes => read_workouts(&FitNotesImporter, &args, out)?,
+        ImportSource::Wger => {
+            let catalog = read_catalog(open(&args.file)?, args.default_type.map(|t| t.into()))?;
+            writeln!(
+                out,
+                "Read {} exercises from wger; {} use equipment that isn't supported",
+                catalog.parsed.exercises.len(),
+                catalog.unsupported
+            )?;
+            catalog.parsed
+        }
+    };
+    let existing = mgr.list().await?;
+    let aliases = mgr.aliases().await?;
+    let deleted = mgr.deleted().await?;
+    let plan = ImportPlan::new(parsed, &ExerciseMatcher::new(&existing, &aliases), &deleted);
+
+    if args.dry_run {
+        for change in &plan.changes {
+            match (&change.matched, &change.suggestion) {
+                (Some(m), _) if m.imported != change.exercise.name => writeln!(
+                    out,
+                    "{:<9}  {} (matched '{}', {:.0}%)",
+                    change.action,
+                    change.exercise.name,
+                    m.imported,
+                    m.confidence * 100.0
+                )?,
+                _ if change.skip_reason.is_some() => write_skipped(out, change)?,
+                (_, Some(s)) => writeln!(
+                    out,
+                    "{:<9}  {} (similar to '{}', {:.0}%)",
+                    change.action,
+                    change.exercise.name,
+                    s.existing,
+                    s.confidence * 100.0
+                )?,
+                _ => writeln!(out, "{:<9}  {}", change.action, change.exercise.name)?,
+            }
+        }
+        for error in &plan.errors {
+            writeln!(out, "{:<9}  {}", "skip", error)?;
+        }
+        writeln!(
+            out,
+            "Dry run: {} to create, {} to update, {} unchanged, {} skipped",
+            plan.count(Action::Create),
+            plan.count(Action::Update),
+            plan.count(Action::Unchanged),
+            plan.count(Action::Skip) + plan.errors.len()
+        )?;
+        if plan.changes.iter().any(|c| c.suggestion.is_some()) {
+            writeln!(
+                out,
+                "Similar names are imported as new exercises, use `trainer exercise alias <exercise> <other name>` for those that are the same"
+            )?;
+        }
+        return Ok(());
+    }
+
+    for error in &plan.errors {
+        writeln!(out, "Skipped {}", error)?;
+    }
+    for change in plan.changes.iter().filter(|c| c.action == Action::Skip) {
+        write_skipped(out, change)?;
+    }
+    let summary = apply(mgr, &plan).await?;
+    for failed in &summary.failed {
+        writeln!(out, "Failed {}: {}", failed.name, failed.error)?;
+    }
+    writeln!(
+        out,
+        "Imported exercises: {} created, {} updated, {} unchanged, {} skipped",
+        summary.created, summary.updated, summary.unchanged, summary.skipped
+    )?;
+    match summary.failed.into_iter().next() {
+        Some(failed) => Err(failed.error.into()),
+        None => Ok(()),
+    }
+}
+
+fn write_skipped<W: Write>(out: &mut W, change: &PlannedChange) -> CliResult<()> {
+    match &change.skip_reason {
+        Some(SkipReason::Deleted(name)) => writeln!(
+            out,
+            "{:<9}  {} (deleted, run `trainer exercise restore \"{}\"` to import it)",
+            Action::Skip,
+            change.exercise.name,
+            name
+        )?,
+        Some(SkipReason::Duplicate) => writeln!(
+            out,
+            "{:<9}  {} (already in the import)",
+            Action::Skip,
+            change.exercise.name
+        )?,
+        None => writeln!(out, "{:<9}  {}", Action::Skip, change.exercise.name)?,
+    }
+    Ok(())
+}
+
+fn open(path: &Path) -> CliResult<BufReader<File>> {
+    File::open(path)
+        .map(BufReader::new)
+        .map_err(|e| CliError::Import(format!("{}: {}", path.display(), e)))
+}
+
+/// Reads the exercises used in the workouts of another app.  The sets themselves are counted, along
+/// with the unit conversions their weights needed, but not stored as there is nowhere to keep
+/// workout history yet.
+fn read_workouts<I, W>(importer: &I, args: &ImportArgs, out: &mut W) -> CliResult<ParsedExercises>
+where
+    I: WorkoutImporter,
+    W: Write,
+{
+    let names = match &args.names {
+        Some(path) => NameMapping::read(open(path)?)?,
+        None => NameMapping::default(),
+    };
+    let workouts = importer.read(&mut open(&args.file)?)?;
+    writeln!(
+        out,
+        "Read {} sets from {}; only their exercises are imported",
+        workouts.sets.len(),
+        importer.source()
+    )?;
+    let converted = workouts.converted();
+    if converted > 0 {
+        writeln!(out, "Converted the weight of {} sets to kg", converted)?;
+    }
+    Ok(workouts.exercises(&names, args.default_type.map(|t| t.into())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use api::{Exercise, ExerciseManager, ExerciseType};
+    use rand::distributions::Alphanumeric;
+    use rand::{thread_rng, Rng};
+    use sqlite::{DBType, SqliteExerciseRepository};
+    use std::fs;
+    use tempfile::{tempdir, TempDir};
+    use test_log::test;
+
+    fn db_name() -> String {
+        let rand_string: String = thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(10)
+            .map(char::from)
+            .collect();
+
+        format!("testdb-{}.db3", rand_string)
+    }
+
+    async fn temp_repo() -> (TempDir, SqliteExerciseRepository) {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join(db_name());
+        let repo = SqliteExerciseRepository::new(DBType::File(file_path.as_path()))
+            .await
+            .unwrap();
+        (dir, repo)
+    }
+
+    fn import_args(file: PathBuf, dry_run: bool) -> ImportArgs {
+        ImportArgs {
+            file,
+            from: ImportSource::Trainer,
+            names: None,
+            mappings: vec![],
+            default_type: None,
+            dry_run,
+        }
+    }
+
+    #[test]
+    fn parse_mapping_ok() {
+        assert_eq!(
+            parse_mapping("name=Exercise Name").unwrap(),
+            (ImportField::Name, "Exercise Name".to_string())
+        );
+        assert_eq!(
+            parse_mapping("TYPE = Category").unwrap(),
+            (ImportField::Type, "Category".to_string())
+        );
+        assert!(parse_mapping("name").is_err());
+        assert!(parse_mapping("weight=kg").is_err());
+    }
+
+    #[test]
+    fn column_mapping_ok() {
+        let args = ImportArgs {
+            mappings: vec![
+                (ImportField::Name, "Exercise".to_string()),
+                (ImportField::Description, "Notes".to_string()),
+            ],
+            default_type: Some(ExerciseTypeArg::Bodyweight),
+            ..import_args(PathBuf::from("in.csv"), false)
+        };
+        assert_eq!(
+            args.column_mapping(),
+            ColumnMapping {
+                name: "Exercise".to_string(),
+                exercise_type: "exercise_type".to_string(),
+                description: "Notes".to_string(),
+                default_type: Some(ExerciseType::BodyWeight),
+            }
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn export_then_import_ok() {
+        let (dir, repo) = temp_repo().await;
+        let mgr = ExerciseManager::new(&repo).unwrap();
+        mgr.save(&mut Exercise {
+            id: None,
+            name: "Deadlift".to_string(),
+            description: Some("From the floor".to_string()),
+            exercise_type: ExerciseType::Barbell,
+            bar_weight: None,
+            load_increment: None,
+        })
+        .await
+        .unwrap();
+
+        let path = dir.path().join("exercises.csv");
+        let mut out = Vec::new();
+        export(
+            &mgr,
+            ExportArgs {
+                format: ExportFormat::Csv,
+                file: Some(path.clone()),
+            },
+            &mut out,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "name,exercise_type,description,bar_weight,load_increment\nDeadlift,barbell,From the floor,,\n"
+        );
+
+        let (_other_dir, other_repo) = temp_repo().await;
+        let other_mgr = ExerciseManager::new(&other_repo).unwrap();
+        let mut out = Vec::new();
+        import(&other_mgr, import_args(path, false), &mut out)
+            .await
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "Imported exercises: 1 created, 0 updated, 0 unchanged, 0 skipped\n"
+        );
+        assert_eq!(other_mgr.list().await.unwrap(), mgr.list().await.unwrap());
+    }
+
+    #[test(tokio::test)]
+    async fn import_dry_run_ok() {
+        let (dir, repo) = temp_repo().await;
+        let mgr = ExerciseManager::new(&repo).unwrap();
+        mgr.save(&mut Exercise {
+            id: None,
+            name: "Squat".to_string(),
+            description: None,
+            exercise_type: ExerciseType::Barbell,
+            bar_weight: None,
+            load_increment: None,
+        })
+        .await
+        .unwrap();
+
+        let path = dir.path().join("import.csv");
+        fs::write(
+            &path,
+            "name,exercise_type,description\nsquat,barbell,High bar\nSwing,kb,\nBand,band,\n",
+        )
+        .unwrap();
+
+        let mut out = Vec::new();
+        import(&mgr, import_args(path, true), &mut out)
+            .await
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "update     Squat (matched 'squat', 100%)\n\
+             create     Swing\n\
+             skip       line 4: unsupported exercise type: band\n\
+             Dry run: 1 to create, 1 to update, 0 unchanged, 1 skipped\n"
+        );
+
+        // Nothing changes on a dry run
+        let exercises = mgr.list().await.unwrap();
+        assert_eq!(exercises.len(), 1);
+        assert_eq!(exercises[0].description, None);
+    }
+
+    #[test(tokio::test)]
+    async fn import_dry_run_matches_aliases() {
+        let (dir, repo) = temp_repo().await;
+        let mgr = ExerciseManager::new(&repo).unwrap();
+        mgr.save(&mut Exercise {
+            id: None,
+            name: "Romanian Deadlift".to_string(),
+            description: None,
+            exercise_type: ExerciseType::Barbell,
+            bar_weight: None,
+            load_increment: None,
+        })
+        .await
+        .unwrap();
+        mgr.add_alias("Romanian Deadlift".to_string(), "RDL".to_string())
+            .await
+            .unwrap();
+
+        let path = dir.path().join("import.csv");
+        fs::write(
+            &path,
+            "name,exercise_type\nrdl,barbell\nRomanian Dead-lifts,barbell\n",
+        )
+        .unwrap();
+
+        let mut out = Vec::new();
+        import(&mgr, import_args(path, true), &mut out)
+            .await
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "unchanged  Romanian Deadlift (matched 'rdl', 100%)\n\
+             create     Romanian Dead-lifts (similar to 'Romanian Deadlift', 94%)\n\
+             Dry run: 1 to create, 0 to update, 1 unchanged, 0 skipped\n\
+             Similar names are imported as new exercises, use `trainer exercise alias <exercise> <other name>` for those that are the same\n"
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn import_skips_deleted_exercises() {
+        let (dir, repo) = temp_repo().await;
+        let mgr = ExerciseManager::new(&repo).unwrap();
+        mgr.save(&mut Exercise {
+            id: None,
+            name: "Beta".to_string(),
+            description: None,
+            exercise_type: ExerciseType::Barbell,
+            bar_weight: None,
+            load_increment: None,
+        })
+        .await
+        .unwrap();
+        mgr.delete("Beta".to_string()).await.unwrap();
+
+        let path = dir.path().join("import.csv");
+        fs::write(
+            &path,
+            "name,exercise_type\nAlpha,barbell\nBeta,barbell\nGamma,barbell\n",
+        )
+        .unwrap();
+
+        let mut out = Vec::new();
+        import(&mgr, import_args(path.clone(), true), &mut out)
+            .await
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "create     Alpha\n\
+             skip       Beta (deleted, run `trainer exercise restore \"Beta\"` to import it)\n\
+             create     Gamma\n\
+             Dry run: 2 to create, 0 to update, 0 unchanged, 1 skipped\n"
+        );
+
+        let mut out = Vec::new();
+        import(&mgr, import_args(path, false), &mut out)
+            .await
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "skip       Beta (deleted, run `trainer exercise restore \"Beta\"` to import it)\n\
+             Imported exercises: 2 created, 0 updated, 0 unchanged, 1 skipped\n"
+        );
+        assert_eq!(mgr.list().await.unwrap().len(), 2);
+    }
+
+    #[test(tokio::test)]
+    async fn import_hevy_ok() {
+        let (dir, repo) = temp_repo().await;
+        let mgr = ExerciseManager::new(&repo).unwrap();
+
+        let path = dir.path().join("workouts.csv");
+        fs::write(
+            &path,
+            "title,start_time,exercise_title,weight_kg,reps\n\
+             Morning,2024-05-01,Deadlift (Barbell),140,5\n\
+             Morning,2024-05-01,Deadlift (Barbell),140,5\n\
+             Morning,2024-05-01,Kettlebell Swing,24,10\n\
+             Morning,2024-05-01,Bench Press (Dumbbell),30,8\n",
+        )
+        .unwrap();
+        let names = dir.path().join("names.csv");
+        fs::write(
+            &names,
+            "source,name,exercise_type\nDeadlift (Barbell),Deadlift,\nKettlebell Swing,Swing,kettlebell\n",
+        )
+        .unwrap();
+
+        let mut out = Vec::new();
+        let args = ImportArgs {
+            from: ImportSource::Hevy,
+            names: Some(names),
+            ..import_args(path, false)
+        };
+        import(&mgr, args, &mut out).await.unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "Read 4 sets from Hevy; only their exercises are imported\n\
+             Skipped line 5: missing exercise type for 'Bench Press (Dumbbell)'\n\
+             Imported exercises: 2 created, 0 updated, 0 unchanged, 1 skipped\n"
+        );
+        let deadlift = mgr.get_by_name("Deadlift".to_string()).await.unwrap();
+        assert_eq!(deadlift.exercise_type, ExerciseType::Barbell);
+        let swing = mgr.get_by_name("Swing".to_string()).await.unwrap();
+        assert_eq!(swing.exercise_type, ExerciseType::KettleBell);
+    }
+
+    #[test(tokio::test)]
+    async fn import_hevy_over_existing_exercises() {
+        let (dir, repo) = temp_repo().await;
+        let mgr = ExerciseManager::new(&repo).unwrap();
+        let mut pull_up = Exercise {
+            id: None,
+            name: "Pull Up".to_string(),
+            description: Some("Overhand grip".to_string()),
+            exercise_type: ExerciseType::BodyWeight,
+            bar_weight: None,
+            load_increment: None,
+        };
+        let mut deadlift = Exercise {
+            name: "Deadlift".to_string(),
+            description: Some("From the floor".to_string()),
+            exercise_type: ExerciseType::Barbell,
+            ..pull_up.clone()
+        };
+        mgr.save(&mut pull_up).await.unwrap();
+        mgr.save(&mut deadlift).await.unwrap();
+
+        let path = dir.path().join("workouts.csv");
+        fs::write(
+            &path,
+            "title,start_time,exercise_title,weight_kg,reps
+             Morning,2024-05-01,Pull Up,,8
+             Morning,2024-05-01,Deadlift (Barbell),140,5
+             Morning,2024-05-01,Front Squat,100,5
+",
+        )
+        .unwrap();
+        let names = dir.path().join("names.csv");
+        fs::write(
+            &names,
+            "source,name,exercise_type
+Deadlift (Barbell),Deadlift,
+",
+        )
+        .unwrap();
+
+        let mut out = Vec::new();
+        let args = ImportArgs {
+            from: ImportSource::Hevy,
+            names: Some(names),
+            default_type: Some(ExerciseTypeArg::Barbell),
+            ..import_args(path, false)
+        };
+        import(&mgr, args, &mut out).await.unwrap();
+        assert!(String::from_utf8(out)
+            .unwrap()
+            .ends_with("Imported exercises: 1 created, 0 updated, 2 unchanged, 0 skipped\n"));
+
+        assert_eq!(
+            mgr.get_by_name("Pull Up".to_string()).await.unwrap(),
+            pull_up
+        );
+        assert_eq!(
+            mgr.get_by_name("Deadlift".to_string()).await.unwrap(),
+            deadlift
+        );
+        let front_squat = mgr.get_by_name("Front Squat".to_string()).await.unwrap();
+        assert_eq!(front_squat.exercise_type, ExerciseType::Barbell);
+    }
+
+    #[test(tokio::test)]
+    async fn import_fitnotes_dry_run() {
+        let (dir, repo) = temp_repo().await;
+        let mgr = ExerciseManager::new(&repo).unwrap();
+
+        let path = dir.path().join("fitnotes.csv");
+        fs::write(
+            &path,
+            "Date,Exercise,Category,Weight (lbs),Reps\n\
+             2024-05-01,Pull Up,Back,,8\n\
+             2024-05-01,Pull Up,Back,25,5\n",
+        )
+        .unwrap();
+
+        let mut out = Vec::new();
+        let args = ImportArgs {
+            from: ImportSource::Fitnotes,
+            default_type: Some(ExerciseTypeArg::Bodyweight),
+            ..import_args(path, true)
+        };
+        import(&mgr, args, &mut out).await.unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "Read 2 sets from FitNotes; only their exercises are imported\n\
+             Converted the weight of 1 sets to kg\n\
+             create     Pull Up\n\
+             Dry run: 1 to create, 0 to update, 0 unchanged, 0 skipped\n"
+        );
+        assert!(mgr.list().await.unwrap().is_empty());
+    }
+
+    #[test(tokio::test)]
+    async fn import_wger_ok() {
+        let (dir, repo) = temp_repo().await;
+        let mgr = ExerciseManager::new(&repo).unwrap();
+
+        let path = dir.path().join("exerciseinfo.json");
+        fs::write(
+            &path,
+            r#"{"results": [
+                {"id": 1, "equipment": [{"name": "Barbell"}],
+                 "translations": [{"name": "Squats", "description": "<p>Deep</p>", "language": 2}]},
+                {"id": 2, "equipment": [{"name": "Dumbbell"}],
+                 "translations": [{"name": "Dumbbell Row", "description": "", "language": 2}]}
+            ]}"#,
+        )
+        .unwrap();
+
+        let mut out = Vec::new();
+        let args = ImportArgs {
+            from: ImportSource::Wger,
+            ..import_args(path, false)
+        };
+        import(&mgr, args, &mut out).await.unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "Read 1 exercises from wger; 1 use equipment that isn't supported\n\
+             Imported exercises: 1 created, 0 updated, 0 unchanged, 0 skipped\n"
+        );
+        let squats = mgr.get_by_name("Squats".to_string()).await.unwrap();
+        assert_eq!(squats.description, Some("Deep".to_string()));
+    }
+
+    #[test(tokio::test)]
+    async fn import_missing_file() {
+        let (dir, repo) = temp_repo().await;
+        let mgr = ExerciseManager::new(&repo).unwrap();
+        let mut out = Vec::new();
+        let result = import(
+            &mgr,
+            import_args(dir.path().join("missing.csv"), false),
+            &mut out,
+        )
+        .await;
+        assert!(matches!(result.err().unwrap(), CliError::Import(_)))
+    }
+}
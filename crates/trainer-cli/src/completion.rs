@@ -0,0 +1,261 @@
+use crate::cli::Cli;
+use crate::config::Config;
+use crate::error::CliResult;
+use crate::settings::Settings;
+use api::ExerciseRepository;
+use clap::{CommandFactory, ValueEnum};
+use clap_complete::engine::CompletionCandidate;
+use clap_complete::env::Shells;
+use sqlite::SqliteExerciseRepository;
+use std::ffi::{OsStr, OsString};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Environment variable the generated shell scripts use to call back into `trainer`.
+pub const COMPLETE_VAR: &str = "COMPLETE";
+
+/// Most exercise names offered for a single completion.
+pub const COMPLETION_LIMIT: u32 = 100;
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum Shell {
+    Bash,
+    Elvish,
+    Fish,
+    Powershell,
+    Zsh,
+}
+
+impl Shell {
+    fn name(&self) -> &'static str {
+        match self {
+            Shell::Bash => "bash",
+            Shell::Elvish => "elvish",
+            Shell::Fish => "fish",
+            Shell::Powershell => "powershell",
+            Shell::Zsh => "zsh",
+        }
+    }
+}
+
+/// Writes the completion script for `shell`.  The script calls back into the `trainer` binary
+/// while completing, which is what allows exercise names to be completed from the database.
+pub fn write_completions<W: Write>(shell: Shell, out: &mut W) -> CliResult<()> {
+    let cmd = Cli::command();
+    let name = cmd.get_name();
+    let completer = std::env::current_exe()
+        .map(|p| p.to_string_lossy().into_owned())
+        .unwrap_or_else(|_| name.to_string());
+
+    // Every variant of Shell is a clap_complete builtin so the lookup can't fail
+    let shells = Shells::builtins();
+    let env_completer = shells.completer(shell.name()).unwrap();
+    env_completer.write_registration(COMPLETE_VAR, name, name, &completer, out)?;
+    Ok(())
+}
+
+/// Renders the man page for `trainer` to `out`, or one page per subcommand into `dir`.
+pub fn write_man<W: Write>(dir: Option<&Path>, out: &mut W) -> CliResult<()> {
+    match dir {
+        Some(dir) => {
+            clap_mangen::generate_to(Cli::command(), dir)?;
+            writeln!(out, "Wrote man pages to {}", dir.display())?;
+        }
+        None => clap_mangen::Man::new(Cli::command()).render(out)?,
+    }
+    Ok(())
+}
+
+/// Completes exercise names from the database the command line being completed would use.
+/// Completion must never fail loudly, so any problem simply results in no candidates.
+pub fn complete_exercise_name(current: &OsStr) -> Vec<CompletionCandidate> {
+    let Some(prefix) = current.to_str() else {
+        return vec![];
+    };
+    let Some(database) = line_database(std::env::args_os()) else {
+        return vec![];
+    };
+
+    exercise_names(&database, prefix)
+        .into_iter()
+        .map(CompletionCandidate::new)
+        .collect()
+}
+
+/// The database of the command line being completed, honoring any `--config`, `--profile` and
+/// `--database` already on it.  `args` are those of the completing process, where the words of
+/// the line follow `--`.
+fn line_database<I: IntoIterator<Item = OsString>>(args: I) -> Option<PathBuf> {
+    let words = args.into_iter().skip_while(|a| a != "--").skip(1);
+    // The line is usually incomplete, so parse whatever is there
+    let matches = Cli::command()
+        .ignore_errors(true)
+        .try_get_matches_from(words)
+        .ok()?;
+    let config = match matches
+        .get_one::<PathBuf>("config")
+        .cloned()
+        .or_else(Config::default_path)
+    {
+        Some(path) => Config::load(&path).ok()?,
+        None => Config::default(),
+    };
+    Settings::from_config(
+        &config,
+        matches.get_one::<String>("profile").map(String::as_str),
+        matches.get_one::<PathBuf>("database").cloned(),
+    )
+    .ok()?
+    .database()
+    .ok()
+}
+
+/// Returns the names of exercises starting with `prefix` (case-insensitive), at most
+/// [`COMPLETION_LIMIT`] of them.  The database is opened read-only, so a missing or outdated
+/// file yields no names rather than being created or migrated as a side effect of completion.
+pub fn exercise_names(database: &Path, prefix: &str) -> Vec<String> {
+    let runtime = match tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+    {
+        Ok(r) => r,
+        Err(_) => return vec![],
+    };
+
+    let exercises = runtime.block_on(async {
+        let repo = SqliteExerciseRepository::open_read_only(database).await?;
+        repo.query_by_prefix(prefix.to_string(), COMPLETION_LIMIT)
+            .await
+    });
+
+    match exercises {
+        Ok(exercises) => exercises.into_iter().map(|e| e.name).collect(),
+        Err(_) => vec![],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use api::{Exercise, ExerciseType};
+    use sqlite::DBType;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn completions_for_every_shell() {
+        for shell in Shell::value_variants() {
+            let mut out = Vec::new();
+            write_completions(*shell, &mut out).unwrap();
+            let script = String::from_utf8(out).unwrap();
+            assert!(script.contains(COMPLETE_VAR), "{:?}", shell);
+        }
+    }
+
+    #[test]
+    fn man_page_to_writer() {
+        let mut out = Vec::new();
+        write_man(None, &mut out).unwrap();
+        let page = String::from_utf8(out).unwrap();
+        assert!(page.starts_with(".ie"));
+        assert!(page.contains(".TH trainer"));
+    }
+
+    #[test]
+    fn man_pages_to_dir() {
+        let dir = tempdir().unwrap();
+        let mut out = Vec::new();
+        write_man(Some(dir.path()), &mut out).unwrap();
+        assert!(dir.path().join("trainer.1").exists());
+        assert!(dir.path().join("trainer-exercise.1").exists());
+        assert!(dir.path().join("trainer-exercise-show.1").exists());
+    }
+
+    #[test]
+    fn exercise_names_missing_database() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("missing.db3");
+        assert!(exercise_names(&path, "").is_empty());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn exercise_names_unmigrated_database() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("empty.db3");
+        fs::File::create(&path).unwrap();
+        assert!(exercise_names(&path, "").is_empty());
+        assert_eq!(fs::metadata(&path).unwrap().len(), 0);
+    }
+
+    #[test]
+    fn line_database_honors_flags() {
+        let dir = tempdir().unwrap();
+        let config = dir.path().join("config.toml");
+        fs::write(
+            &config,
+            "default_profile = \"home\"\n[profiles.home]\ndatabase = \"/data/home.db3\"\n[profiles.gym]\ndatabase = \"/data/gym.db3\"\n",
+        )
+        .unwrap();
+        let line = |words: &[&str]| {
+            let args = [
+                "trainer",
+                "--",
+                "trainer",
+                "--config",
+                config.to_str().unwrap(),
+            ]
+            .iter()
+            .chain(words)
+            .map(OsString::from)
+            .collect::<Vec<_>>();
+            line_database(args)
+        };
+
+        assert_eq!(
+            line(&["exercise", "show", "De"]),
+            Some(PathBuf::from("/data/home.db3"))
+        );
+        assert_eq!(
+            line(&["exercise", "show", "-p", "gym", "De"]),
+            Some(PathBuf::from("/data/gym.db3"))
+        );
+        assert_eq!(
+            line(&["--database", "/tmp/other.db3", "max", ""]),
+            Some(PathBuf::from("/tmp/other.db3"))
+        );
+        assert_eq!(line(&["--profile", "work", "max", ""]), None);
+    }
+
+    #[test]
+    fn exercise_names_by_prefix() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("trainer.db3");
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            let repo = SqliteExerciseRepository::new(DBType::File(path.as_path()))
+                .await
+                .unwrap();
+            for name in ["Deadlift", "Dips", "Squat"] {
+                repo.create(&Exercise {
+                    id: None,
+                    name: name.to_string(),
+                    description: None,
+                    exercise_type: ExerciseType::Barbell,
+                    bar_weight: None,
+                    load_increment: None,
+                })
+                .await
+                .unwrap();
+            }
+        });
+
+        assert_eq!(exercise_names(&path, "d"), vec!["Deadlift", "Dips"]);
+        assert_eq!(exercise_names(&path, "SQ"), vec!["Squat"]);
+        assert_eq!(exercise_names(&path, "").len(), 3);
+        assert!(exercise_names(&path, "bench").is_empty());
+    }
+}
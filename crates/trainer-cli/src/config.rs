@@ -0,0 +1,309 @@
+use crate::error::{CliError, CliResult};
+use api::WeightUnit;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Name of the profile used when no profile is requested and none is marked as the default.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Weight unit preference of a profile.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Units {
+    #[default]
+    Kg,
+    Lb,
+}
+
+impl From<Units> for WeightUnit {
+    fn from(value: Units) -> Self {
+        match value {
+            Units::Kg => WeightUnit::Kg,
+            Units::Lb => WeightUnit::Lb,
+        }
+    }
+}
+
+impl std::fmt::Display for Units {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Units::Kg => write!(f, "kg"),
+            Units::Lb => write!(f, "lb"),
+        }
+    }
+}
+
+/// A named set of values in the configuration file.  Every value is optional so a profile only
+/// needs to mention what differs from the defaults.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Profile {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub database: Option<PathBuf>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote_url: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ca_cert: Option<PathBuf>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_cert: Option<PathBuf>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_key: Option<PathBuf>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub units: Option<Units>,
+
+    /// Language of the messages, e.g. `es`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+
+    /// Database statements still running after this many milliseconds are interrupted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query_timeout_ms: Option<u64>,
+
+    /// Database statements taking longer than this many milliseconds are logged as warnings
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slow_query_ms: Option<u64>,
+}
+
+/// The keys of a [`Profile`] that can be read and written with `trainer config`.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum ConfigKey {
+    Database,
+    RemoteUrl,
+    CaCert,
+    ClientCert,
+    ClientKey,
+    Units,
+    Locale,
+    QueryTimeoutMs,
+    SlowQueryMs,
+}
+
+impl Profile {
+    pub fn get(&self, key: ConfigKey) -> Option<String> {
+        match key {
+            ConfigKey::Database => self.database.as_ref().map(|p| p.display().to_string()),
+            ConfigKey::RemoteUrl => self.remote_url.clone(),
+            ConfigKey::CaCert => self.ca_cert.as_ref().map(|p| p.display().to_string()),
+            ConfigKey::ClientCert => self.client_cert.as_ref().map(|p| p.display().to_string()),
+            ConfigKey::ClientKey => self.client_key.as_ref().map(|p| p.display().to_string()),
+            ConfigKey::Units => self.units.map(|u| u.to_string()),
+            ConfigKey::Locale => self.locale.clone(),
+            ConfigKey::QueryTimeoutMs => self.query_timeout_ms.map(|ms| ms.to_string()),
+            ConfigKey::SlowQueryMs => self.slow_query_ms.map(|ms| ms.to_string()),
+        }
+    }
+
+    pub fn set(&mut self, key: ConfigKey, value: &str) -> CliResult<()> {
+        match key {
+            ConfigKey::Database => self.database = Some(PathBuf::from(value)),
+            ConfigKey::RemoteUrl => self.remote_url = Some(value.to_string()),
+            ConfigKey::CaCert => self.ca_cert = Some(PathBuf::from(value)),
+            ConfigKey::ClientCert => self.client_cert = Some(PathBuf::from(value)),
+            ConfigKey::ClientKey => self.client_key = Some(PathBuf::from(value)),
+            ConfigKey::Units => {
+                let units = Units::from_str(value, true)
+                    .map_err(|_| CliError::Config(format!("'{}' is not a valid unit", value)))?;
+                self.units = Some(units)
+            }
+            ConfigKey::Locale => {
+                i18n::Localizer::new(value)
+                    .map_err(|_| CliError::Config(format!("'{}' is not a valid locale", value)))?;
+                self.locale = Some(value.to_string())
+            }
+            ConfigKey::QueryTimeoutMs => self.query_timeout_ms = Some(millis(value)?),
+            ConfigKey::SlowQueryMs => self.slow_query_ms = Some(millis(value)?),
+        }
+        Ok(())
+    }
+
+    pub fn unset(&mut self, key: ConfigKey) {
+        match key {
+            ConfigKey::Database => self.database = None,
+            ConfigKey::RemoteUrl => self.remote_url = None,
+            ConfigKey::CaCert => self.ca_cert = None,
+            ConfigKey::ClientCert => self.client_cert = None,
+            ConfigKey::ClientKey => self.client_key = None,
+            ConfigKey::Units => self.units = None,
+            ConfigKey::Locale => self.locale = None,
+            ConfigKey::QueryTimeoutMs => self.query_timeout_ms = None,
+            ConfigKey::SlowQueryMs => self.slow_query_ms = None,
+        }
+    }
+}
+
+fn millis(value: &str) -> CliResult<u64> {
+    value
+        .parse()
+        .map_err(|_| CliError::Config(format!("'{}' is not a number of milliseconds", value)))
+}
+
+/// Contents of `config.toml`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_profile: Option<String>,
+
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+impl Config {
+    /// `$XDG_CONFIG_HOME/trainer/config.toml`, falling back to `~/.config/trainer/config.toml`.
+    pub fn default_path() -> Option<PathBuf> {
+        let base = match std::env::var_os("XDG_CONFIG_HOME") {
+            Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+            _ => PathBuf::from(std::env::var_os("HOME")?).join(".config"),
+        };
+        Some(base.join("trainer").join("config.toml"))
+    }
+
+    /// Loads the configuration at `path`.  A missing file is an empty configuration.
+    pub fn load(path: &Path) -> CliResult<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let contents = fs::read_to_string(path)
+            .map_err(|e| CliError::Config(format!("unable to read {}: {}", path.display(), e)))?;
+        toml::from_str(&contents)
+            .map_err(|e| CliError::Config(format!("unable to parse {}: {}", path.display(), e)))
+    }
+
+    pub fn save(&self, path: &Path) -> CliResult<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let contents = toml::to_string_pretty(self).map_err(|e| CliError::Config(e.to_string()))?;
+        fs::write(path, contents)
+            .map_err(|e| CliError::Config(format!("unable to write {}: {}", path.display(), e)))
+    }
+
+    /// The profile to use when the caller did not ask for one.
+    pub fn active_profile_name(&self) -> &str {
+        self.default_profile.as_deref().unwrap_or(DEFAULT_PROFILE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    const SAMPLE: &str = r#"
+default_profile = "gym"
+
+[profiles.home]
+database = "/data/trainer.db3"
+
+[profiles.gym]
+remote_url = "https://trainer.example.com"
+client_cert = "/certs/client.pem"
+units = "lb"
+"#;
+
+    #[test]
+    fn load_missing_file() {
+        let dir = tempdir().unwrap();
+        let config = Config::load(&dir.path().join("config.toml")).unwrap();
+        assert_eq!(config, Config::default());
+        assert_eq!(config.active_profile_name(), DEFAULT_PROFILE);
+    }
+
+    #[test]
+    fn load_profiles() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(&path, SAMPLE).unwrap();
+
+        let config = Config::load(&path).unwrap();
+        assert_eq!(config.active_profile_name(), "gym");
+        assert_eq!(
+            config.profiles["home"].database,
+            Some(PathBuf::from("/data/trainer.db3"))
+        );
+        let gym = &config.profiles["gym"];
+        assert_eq!(gym.units, Some(Units::Lb));
+        assert_eq!(
+            gym.get(ConfigKey::RemoteUrl),
+            Some("https://trainer.example.com".to_string())
+        );
+        assert_eq!(gym.database, None);
+    }
+
+    #[test]
+    fn load_invalid_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(&path, "[profiles.home]\nunknown = 1\n").unwrap();
+
+        let result = Config::load(&path);
+        assert!(matches!(result.err().unwrap(), CliError::Config(_)))
+    }
+
+    #[test]
+    fn save_and_load() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("nested").join("config.toml");
+
+        let mut config = Config::default();
+        let profile = config.profiles.entry("home".to_string()).or_default();
+        profile
+            .set(ConfigKey::Database, "/data/trainer.db3")
+            .unwrap();
+        profile.set(ConfigKey::Units, "LB").unwrap();
+        config.save(&path).unwrap();
+
+        assert_eq!(Config::load(&path).unwrap(), config);
+    }
+
+    #[test]
+    fn set_and_unset() {
+        let mut profile = Profile::default();
+        for key in ConfigKey::value_variants() {
+            assert_eq!(profile.get(*key), None);
+        }
+
+        profile.set(ConfigKey::ClientKey, "/certs/key.pem").unwrap();
+        assert_eq!(
+            profile.get(ConfigKey::ClientKey),
+            Some("/certs/key.pem".to_string())
+        );
+        profile.unset(ConfigKey::ClientKey);
+        assert_eq!(profile.get(ConfigKey::ClientKey), None);
+    }
+
+    #[test]
+    fn set_invalid_units() {
+        let mut profile = Profile::default();
+        let result = profile.set(ConfigKey::Units, "stone");
+        assert!(matches!(result.err().unwrap(), CliError::Config(_)));
+        assert_eq!(profile.units, None);
+    }
+
+    #[test]
+    fn set_locale() {
+        let mut profile = Profile::default();
+        profile.set(ConfigKey::Locale, "es-MX").unwrap();
+        assert_eq!(profile.get(ConfigKey::Locale), Some("es-MX".to_string()));
+        let result = profile.set(ConfigKey::Locale, "not a locale");
+        assert!(matches!(result.err().unwrap(), CliError::Config(_)));
+    }
+
+    #[test]
+    fn set_millis() {
+        let mut profile = Profile::default();
+        profile.set(ConfigKey::SlowQueryMs, "250").unwrap();
+        assert_eq!(profile.slow_query_ms, Some(250));
+        let result = profile.set(ConfigKey::QueryTimeoutMs, "5s");
+        assert!(matches!(result.err().unwrap(), CliError::Config(_)));
+        assert_eq!(profile.query_timeout_ms, None);
+    }
+}
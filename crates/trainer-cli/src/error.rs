@@ -0,0 +1,229 @@
+use crate::locale::{args, exercise_error, repository_error};
+use crate::output::{write_one, OutputFormat};
+use api::{ExerciseError, RepositoryError, TrainerErrorCode};
+use i18n::Localizer;
+use serde::Serialize;
+use std::io::Write;
+use transfer::TransferError;
+
+pub type CliResult<T, E = CliError> = Result<T, E>;
+
+#[derive(thiserror::Error, Debug)]
+#[non_exhaustive]
+pub enum CliError {
+    #[error("no database configured for profile '{0}', pass --database or run `trainer config set database <path>`")]
+    MissingDatabase(String),
+
+    #[error("profile '{0}' does not exist in the configuration file")]
+    UnknownProfile(String),
+
+    #[error("{0} is not set for profile '{1}'")]
+    ConfigValueNotSet(String, String),
+
+    #[error("configuration error: {0}")]
+    Config(String),
+
+    #[error("exercise '{0}' was not found")]
+    ExerciseNotFound(String),
+
+    #[error("no exercise was selected, pass its name")]
+    NoExerciseSelected,
+
+    #[error("this command needs an interactive terminal")]
+    NoTerminal,
+
+    #[error("notify command '{0}' failed: {1}")]
+    NotifyFailed(String, String),
+
+    #[error("exercise operation failed: {0}")]
+    Exercise(#[from] ExerciseError),
+
+    #[error("repository operation failed: {0}")]
+    Repository(#[from] RepositoryError),
+
+    #[error("the database integrity check found {0} problems, restore it from a backup")]
+    IntegrityCheckFailed(usize),
+
+    #[error("unable to read import file {0}")]
+    Import(String),
+
+    #[error("transfer failed: {0}")]
+    Transfer(#[from] TransferError),
+
+    #[error("unable to format output: {0}")]
+    Format(String),
+
+    #[error("unable to write output: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl CliError {
+    pub fn code(&self) -> TrainerErrorCode {
+        match self {
+            CliError::MissingDatabase(_) => TrainerErrorCode::MissingDatabase,
+            CliError::UnknownProfile(_) => TrainerErrorCode::UnknownProfile,
+            CliError::ConfigValueNotSet(_, _) => TrainerErrorCode::ConfigValueNotSet,
+            CliError::Config(_) => TrainerErrorCode::InvalidConfig,
+            CliError::ExerciseNotFound(_) => TrainerErrorCode::ExerciseNotFound,
+            CliError::NoExerciseSelected => TrainerErrorCode::NoExerciseSelected,
+            CliError::NoTerminal => TrainerErrorCode::NoTerminal,
+            CliError::NotifyFailed(_, _) => TrainerErrorCode::NotifyFailed,
+            CliError::Exercise(e) => e.code(),
+            CliError::Repository(e) => e.code(),
+            CliError::IntegrityCheckFailed(_) => TrainerErrorCode::IntegrityCheckFailed,
+            CliError::Import(_) | CliError::Io(_) => TrainerErrorCode::IoError,
+            CliError::Transfer(e) => e.code(),
+            CliError::Format(_) => TrainerErrorCode::Unknown,
+        }
+    }
+
+    /// The message of the error in the locale of `localizer`.
+    pub fn localized(&self, localizer: &Localizer) -> String {
+        let (id, values) = match self {
+            CliError::MissingDatabase(p) => ("cli-missing-database", vec![("profile", p.clone())]),
+            CliError::UnknownProfile(p) => ("cli-unknown-profile", vec![("profile", p.clone())]),
+            CliError::ConfigValueNotSet(k, p) => (
+                "cli-config-value-not-set",
+                vec![("key", k.clone()), ("profile", p.clone())],
+            ),
+            CliError::Config(d) => ("cli-config", vec![("detail", d.clone())]),
+            CliError::ExerciseNotFound(n) => ("cli-exercise-not-found", vec![("name", n.clone())]),
+            CliError::NoExerciseSelected => ("cli-no-exercise-selected", vec![]),
+            CliError::NoTerminal => ("cli-no-terminal", vec![]),
+            CliError::NotifyFailed(c, d) => (
+                "cli-notify-failed",
+                vec![("command", c.clone()), ("detail", d.clone())],
+            ),
+            CliError::Exercise(e) => (
+                "cli-exercise",
+                vec![("reason", exercise_error(localizer, e))],
+            ),
+            CliError::Repository(e) => (
+                "cli-repository",
+                vec![("reason", repository_error(localizer, e))],
+            ),
+            CliError::IntegrityCheckFailed(n) => {
+                ("cli-integrity-check-failed", vec![("count", n.to_string())])
+            }
+            CliError::Import(d) => ("cli-import", vec![("detail", d.clone())]),
+            CliError::Transfer(e) => ("cli-transfer", vec![("reason", e.to_string())]),
+            CliError::Format(d) => ("cli-format", vec![("detail", d.clone())]),
+            CliError::Io(e) => ("cli-io", vec![("detail", e.to_string())]),
+        };
+        localizer.message(id, Some(&args(&values)))
+    }
+}
+
+/// An error as written by [`write_error`] in the structured output formats.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ErrorReport {
+    pub code: TrainerErrorCode,
+    pub message: String,
+}
+
+/// Writes `error` with its code, as `error[CODE]: message` for [`OutputFormat::Table`] and as an
+/// [`ErrorReport`] otherwise so scripts can match on the code.  The message is localized, the code
+/// never is.
+pub fn write_error<W: Write>(
+    out: &mut W,
+    format: OutputFormat,
+    error: &CliError,
+    localizer: &Localizer,
+) -> CliResult<()> {
+    let report = ErrorReport {
+        code: error.code(),
+        message: error.localized(localizer),
+    };
+    write_one(out, format, &report, |out, report| {
+        writeln!(out, "error[{}]: {}", report.code, report.message)?;
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::locale::localizer;
+
+    fn written(format: OutputFormat, error: &CliError) -> String {
+        let mut out = Vec::new();
+        write_error(&mut out, format, error, &localizer("en")).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn code_ok() {
+        assert_eq!(
+            CliError::Exercise(ExerciseError::ExerciseNotFoundError).code(),
+            TrainerErrorCode::ExerciseNotFound
+        );
+        assert_eq!(
+            CliError::Transfer(TransferError::MissingColumnError("name".to_string())).code(),
+            TrainerErrorCode::MissingColumn
+        );
+        assert_eq!(
+            CliError::Repository(RepositoryError::ItemNotFoundError).code(),
+            TrainerErrorCode::NotFound
+        );
+    }
+
+    #[test]
+    fn write_error_ok() {
+        let error = CliError::UnknownProfile("work".to_string());
+        assert_eq!(
+            written(OutputFormat::Table, &error),
+            "error[UNKNOWN_PROFILE]: profile 'work' does not exist in the configuration file\n"
+        );
+
+        let json: serde_json::Value =
+            serde_json::from_str(&written(OutputFormat::Json, &error)).unwrap();
+        assert_eq!(json["code"], "UNKNOWN_PROFILE");
+        assert_eq!(
+            json["message"],
+            "profile 'work' does not exist in the configuration file"
+        );
+    }
+
+    #[test]
+    fn localized_english_matches_display() {
+        let errors = [
+            CliError::MissingDatabase("home".to_string()),
+            CliError::UnknownProfile("work".to_string()),
+            CliError::ConfigValueNotSet("units".to_string(), "home".to_string()),
+            CliError::Config("bad".to_string()),
+            CliError::ExerciseNotFound("Squat".to_string()),
+            CliError::NoExerciseSelected,
+            CliError::NoTerminal,
+            CliError::NotifyFailed("notify-send".to_string(), "exit status: 1".to_string()),
+            CliError::Exercise(ExerciseError::RestoreFailed),
+            CliError::Exercise(ExerciseError::DuplicateNameError),
+            CliError::Repository(RepositoryError::DuplicateNameError("Squat".to_string())),
+            CliError::Repository(RepositoryError::QueryError("disk I/O error".to_string())),
+            CliError::Repository(RepositoryError::SchemaMismatchError {
+                database: 1000,
+                supported: 5,
+            }),
+            CliError::IntegrityCheckFailed(3),
+            CliError::Import("x.csv".to_string()),
+            CliError::Transfer(TransferError::CsvError("bad quote".to_string())),
+            CliError::Format("bad".to_string()),
+        ];
+        let localizer = localizer("en");
+        for error in errors {
+            assert_eq!(error.localized(&localizer), error.to_string());
+        }
+    }
+
+    #[test]
+    fn localized_spanish() {
+        let localizer = localizer("es");
+        assert_eq!(
+            CliError::ExerciseNotFound("Sentadilla".to_string()).localized(&localizer),
+            "no se encontró el ejercicio 'Sentadilla'"
+        );
+        assert_eq!(
+            CliError::Exercise(ExerciseError::ExerciseNotFoundError).localized(&localizer),
+            "la operación sobre el ejercicio falló: ejercicio no encontrado"
+        );
+    }
+}
@@ -0,0 +1,93 @@
+pub mod cli;
+pub mod commands;
+pub mod completion;
+pub mod config;
+mod error;
+pub mod locale;
+pub mod output;
+pub mod picker;
+mod settings;
+pub mod tui;
+
+pub use self::cli::*;
+pub use self::error::*;
+pub use self::settings::*;
+
+use api::ExerciseManager;
+use sqlite::{DBType, SqliteExerciseRepository};
+use std::io::Write;
+use tracing::instrument;
+
+/// Executes the parsed command line, writing any command output to `out`.
+#[instrument(skip(cli, out))]
+pub async fn run<W: Write>(cli: Cli, out: &mut W) -> CliResult<()> {
+    // Commands that don't touch the database must keep working without any configuration
+    let settings = Settings::from_cli(&cli);
+    let config_path = config_path(&cli);
+
+    match cli.command {
+        Command::Exercise(command) => {
+            let repo = open_repository(&settings?).await?;
+            let mgr = ExerciseManager::new(&repo)?;
+            commands::exercise::run(&mgr, command, cli.output, out).await
+        }
+        Command::Export(args) => {
+            let repo = open_repository(&settings?).await?;
+            let mgr = ExerciseManager::new(&repo)?;
+            commands::transfer::export(&mgr, args, out).await
+        }
+        Command::Import(args) => {
+            let repo = open_repository(&settings?).await?;
+            let mgr = ExerciseManager::new(&repo)?;
+            commands::transfer::import(&mgr, args, out).await
+        }
+        Command::Max(args) => {
+            let settings = settings?;
+            let repo = open_repository(&settings).await?;
+            let mgr = ExerciseManager::new(&repo)?;
+            commands::max::run(&mgr, args, settings.units, cli.output, out).await
+        }
+        Command::Tui => {
+            let repo = open_repository(&settings?).await?;
+            let mgr = ExerciseManager::new(&repo)?;
+            tui::run(&mgr).await
+        }
+        Command::Db(command) => {
+            let repo = open_repository(&settings?).await?;
+            commands::db::run(&repo, command, cli.output, out).await
+        }
+        Command::Init(args) => {
+            commands::init::run(
+                config_path.as_deref(),
+                cli.profile.as_deref(),
+                cli.database,
+                args,
+                cli.output,
+                out,
+            )
+            .await
+        }
+        Command::Config(command) => commands::config::run(
+            command,
+            config_path.as_deref(),
+            cli.profile.as_deref(),
+            cli.output,
+            out,
+        ),
+        Command::Timer(args) => commands::timer::run(args, out).await,
+        Command::Schema { name } => commands::schema::run(&name, out),
+        Command::Completions { shell } => completion::write_completions(shell, out),
+        Command::Man { dir } => completion::write_man(dir.as_deref(), out),
+    }
+}
+
+pub(crate) async fn open_repository(settings: &Settings) -> CliResult<SqliteExerciseRepository> {
+    let database = settings.database()?;
+    if let Some(parent) = database.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let repo =
+        SqliteExerciseRepository::with_options(DBType::File(&database), settings.query_options)
+            .await?;
+    Ok(repo)
+}
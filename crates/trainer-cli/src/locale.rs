@@ -0,0 +1,110 @@
+//! The CLI's message bundles and the localized messages of the errors it reports.
+use api::{ExerciseError, RepositoryError};
+use i18n::{FluentArgs, Localizer, FALLBACK_LOCALE};
+
+/// Locales the CLI has bundles for, as accepted by the `locale` setting.
+pub const LOCALES: [(&str, &str); 2] = [
+    ("en", include_str!("../locales/en.ftl")),
+    ("es", include_str!("../locales/es.ftl")),
+];
+
+/// A localizer for `locale` with the CLI bundles registered.  An unusable locale falls back to
+/// English, as the error that would report it has to be shown in some language.
+pub fn localizer(locale: &str) -> Localizer {
+    let mut localizer =
+        Localizer::new(locale).unwrap_or_else(|_| Localizer::new(FALLBACK_LOCALE).unwrap());
+    for (locale, source) in LOCALES {
+        // The bundles are checked by the tests, so registering them can't fail
+        localizer.register(locale, source).unwrap();
+    }
+    localizer
+}
+
+pub(crate) fn args<'a>(values: &[(&'a str, String)]) -> FluentArgs<'a> {
+    let mut args = FluentArgs::new();
+    for (name, value) in values {
+        args.set(*name, value.clone());
+    }
+    args
+}
+
+pub(crate) fn exercise_error(localizer: &Localizer, error: &ExerciseError) -> String {
+    let id = match error {
+        ExerciseError::ExerciseNotFoundError => "error-exercise-not-found",
+        ExerciseError::LookupError => "error-lookup-failed",
+        ExerciseError::SaveFailed => "error-save-failed",
+        ExerciseError::DeleteFailed => "error-delete-failed",
+        ExerciseError::RestoreFailed => "error-restore-failed",
+        ExerciseError::DuplicateNameError => "error-duplicate-name",
+        _ => "error-unknown",
+    };
+    localizer.message(id, None)
+}
+
+pub(crate) fn repository_error(localizer: &Localizer, error: &RepositoryError) -> String {
+    let (id, values) = match error {
+        RepositoryError::PersistenceError(d) => ("error-persistence-failed", detail(d)),
+        RepositoryError::ConnectionError(d) => ("error-connection-failed", detail(d)),
+        RepositoryError::QueryError(d) => ("error-query-failed", detail(d)),
+        RepositoryError::DeleteError(d) => ("error-repository-delete-failed", detail(d)),
+        RepositoryError::ItemNotFoundError => ("error-item-not-found", vec![]),
+        RepositoryError::DuplicateIdError => ("error-duplicate-id", vec![]),
+        RepositoryError::DuplicateNameError(name) => (
+            "error-repository-duplicate-name",
+            vec![("name", name.clone())],
+        ),
+        RepositoryError::SchemaMismatchError {
+            database,
+            supported,
+        } => (
+            "error-schema-mismatch",
+            vec![
+                ("database", database.to_string()),
+                ("supported", supported.to_string()),
+            ],
+        ),
+        RepositoryError::UnknownError(d) => ("error-repository-unknown", detail(d)),
+        // Errors added to the api crate after this mapping are at least shown in English
+        e => return e.to_string(),
+    };
+    localizer.message(id, Some(&args(&values)))
+}
+
+fn detail(detail: &str) -> Vec<(&'static str, String)> {
+    vec![("detail", detail.to_string())]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use i18n::Localizer;
+
+    #[test]
+    fn bundles_register() {
+        let mut localizer = Localizer::new("en").unwrap();
+        for (locale, source) in LOCALES {
+            localizer.register(locale, source).unwrap();
+        }
+    }
+
+    #[test]
+    fn bundles_complete() {
+        let ids = |source: &str| -> Vec<String> {
+            source
+                .lines()
+                .filter(|l| !l.starts_with('#') && l.contains(" = "))
+                .map(|l| l.split(" = ").next().unwrap().to_string())
+                .collect()
+        };
+        let english = ids(LOCALES[0].1);
+        for (locale, source) in &LOCALES[1..] {
+            assert_eq!(ids(source), english, "{} is missing messages", locale);
+        }
+    }
+
+    #[test]
+    fn localizer_invalid_locale() {
+        assert_eq!(localizer("not a locale").locale(), "en");
+        assert_eq!(localizer("es-MX").locale(), "es-MX");
+    }
+}
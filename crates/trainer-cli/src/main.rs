@@ -0,0 +1,54 @@
+use clap::{CommandFactory, Parser};
+use clap_complete::CompleteEnv;
+use std::io;
+use std::process::ExitCode;
+use tracing_subscriber::EnvFilter;
+use trainer_cli::completion::COMPLETE_VAR;
+use trainer_cli::{Cli, Settings};
+
+fn main() -> ExitCode {
+    // Answers completion requests from the shell scripts written by `trainer completions`.  This
+    // has to run before the runtime is started as the completers drive their own runtime.
+    CompleteEnv::with_factory(Cli::command)
+        .var(COMPLETE_VAR)
+        .complete();
+
+    // Only the slow statements of `slow_query_ms` are shown, everything else trainer traces is
+    // reported through its errors
+    tracing_subscriber::fmt()
+        .with_writer(io::stderr)
+        .with_env_filter(EnvFilter::new("sqlx::query=warn"))
+        .init();
+
+    let cli = Cli::parse();
+    let runtime = match tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+    {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("error: unable to start runtime: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let format = cli.error_format();
+    // Built before running so even a broken configuration is reported in the chosen language
+    let localizer = trainer_cli::locale::localizer(
+        &Settings::from_cli(&cli)
+            .map(|s| s.locale)
+            .unwrap_or_default(),
+    );
+    let mut stdout = io::stdout().lock();
+    match runtime.block_on(trainer_cli::run(cli, &mut stdout)) {
+        Ok(_) => ExitCode::SUCCESS,
+        Err(e) => {
+            if let Some(format) = format {
+                if trainer_cli::write_error(&mut io::stderr(), format, &e, &localizer).is_err() {
+                    eprintln!("error: {}", e);
+                }
+            }
+            ExitCode::from(e.code().exit_code())
+        }
+    }
+}
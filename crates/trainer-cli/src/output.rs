@@ -0,0 +1,211 @@
+use crate::error::{CliError, CliResult};
+use clap::ValueEnum;
+use serde::Serialize;
+use std::io::Write;
+
+/// Output format used by the list and show commands.  Every format other than `table` uses the
+/// serialized field names of the underlying model, which are kept stable for scripting.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
+pub enum OutputFormat {
+    #[default]
+    Table,
+    Json,
+    Csv,
+    Yaml,
+}
+
+/// An item written by [`write_list`].  `COLUMNS` are its serialized field names in order, so the
+/// CSV header is written even for an empty list.
+pub trait CsvRow: Serialize {
+    const COLUMNS: &'static [&'static str];
+}
+
+/// Writes a collection of items in the requested format.  `table` is only invoked for
+/// [`OutputFormat::Table`] so each command keeps control over its human readable layout.
+pub fn write_list<W, T, F>(
+    out: &mut W,
+    format: OutputFormat,
+    items: &[T],
+    table: F,
+) -> CliResult<()>
+where
+    W: Write,
+    T: CsvRow,
+    F: FnOnce(&mut W, &[T]) -> CliResult<()>,
+{
+    match format {
+        OutputFormat::Table => table(out, items),
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut *out, items).map_err(format_error)?;
+            writeln!(out)?;
+            Ok(())
+        }
+        OutputFormat::Csv if items.is_empty() => {
+            let mut writer = csv::Writer::from_writer(out);
+            writer.write_record(T::COLUMNS).map_err(format_error)?;
+            writer.flush()?;
+            Ok(())
+        }
+        OutputFormat::Csv => write_csv(out, items),
+        OutputFormat::Yaml => serde_yaml::to_writer(out, items).map_err(format_error),
+    }
+}
+
+/// Writes a single item in the requested format.
+pub fn write_one<W, T, F>(out: &mut W, format: OutputFormat, item: &T, table: F) -> CliResult<()>
+where
+    W: Write,
+    T: Serialize,
+    F: FnOnce(&mut W, &T) -> CliResult<()>,
+{
+    match format {
+        OutputFormat::Table => table(out, item),
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut *out, item).map_err(format_error)?;
+            writeln!(out)?;
+            Ok(())
+        }
+        OutputFormat::Csv => write_csv(out, std::slice::from_ref(item)),
+        OutputFormat::Yaml => serde_yaml::to_writer(out, item).map_err(format_error),
+    }
+}
+
+/// Writes `items` as CSV rows, for commands whose model nests lists that CSV can't hold and so
+/// write a flattened list of rows instead.
+pub fn write_csv<W: Write, T: Serialize>(out: &mut W, items: &[T]) -> CliResult<()> {
+    let mut writer = csv::Writer::from_writer(out);
+    for item in items {
+        writer.serialize(item).map_err(format_error)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+fn format_error<E: std::fmt::Display>(err: E) -> CliError {
+    CliError::Format(err.to_string())
+}
+
+/// Checks that the `COLUMNS` of `T` are the header `item` is serialized with.
+#[cfg(test)]
+pub fn assert_csv_columns<T: CsvRow>(item: &T) {
+    let mut out = Vec::new();
+    write_csv(&mut out, std::slice::from_ref(item)).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert_eq!(out.lines().next(), Some(T::COLUMNS.join(",").as_str()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Row {
+        id: i64,
+        name: String,
+        note: Option<String>,
+    }
+
+    impl CsvRow for Row {
+        const COLUMNS: &'static [&'static str] = &["id", "name", "note"];
+    }
+
+    fn rows() -> Vec<Row> {
+        vec![
+            Row {
+                id: 1,
+                name: "Squat".to_string(),
+                note: None,
+            },
+            Row {
+                id: 2,
+                name: "Bench, Press".to_string(),
+                note: Some("paused".to_string()),
+            },
+        ]
+    }
+
+    fn list(format: OutputFormat) -> String {
+        let mut out = Vec::new();
+        write_list(&mut out, format, &rows(), |out, items| {
+            writeln!(out, "{} rows", items.len())?;
+            Ok(())
+        })
+        .unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    fn one(format: OutputFormat) -> String {
+        let mut out = Vec::new();
+        write_one(&mut out, format, &rows()[1], |out, item| {
+            writeln!(out, "{}", item.name)?;
+            Ok(())
+        })
+        .unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn list_table_uses_callback() {
+        assert_eq!(list(OutputFormat::Table), "2 rows\n");
+    }
+
+    #[test]
+    fn list_json() {
+        let value: serde_json::Value = serde_json::from_str(&list(OutputFormat::Json)).unwrap();
+        assert_eq!(value[0]["name"], "Squat");
+        assert_eq!(value[0]["note"], serde_json::Value::Null);
+        assert_eq!(value[1]["id"], 2);
+    }
+
+    #[test]
+    fn list_csv() {
+        assert_eq!(
+            list(OutputFormat::Csv),
+            "id,name,note\n1,Squat,\n2,\"Bench, Press\",paused\n"
+        );
+    }
+
+    #[test]
+    fn list_csv_empty() {
+        let mut out = Vec::new();
+        write_list::<_, Row, _>(&mut out, OutputFormat::Csv, &[], |_, _| Ok(())).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "id,name,note\n");
+        assert_csv_columns(&rows()[0]);
+    }
+
+    #[test]
+    fn list_yaml() {
+        assert_eq!(
+            list(OutputFormat::Yaml),
+            "- id: 1\n  name: Squat\n  note: null\n- id: 2\n  name: Bench, Press\n  note: paused\n"
+        );
+    }
+
+    #[test]
+    fn one_table_uses_callback() {
+        assert_eq!(one(OutputFormat::Table), "Bench, Press\n");
+    }
+
+    #[test]
+    fn one_json() {
+        let value: serde_json::Value = serde_json::from_str(&one(OutputFormat::Json)).unwrap();
+        assert!(value.is_object());
+        assert_eq!(value["note"], "paused");
+    }
+
+    #[test]
+    fn one_csv() {
+        assert_eq!(
+            one(OutputFormat::Csv),
+            "id,name,note\n2,\"Bench, Press\",paused\n"
+        );
+    }
+
+    #[test]
+    fn one_yaml() {
+        assert_eq!(
+            one(OutputFormat::Yaml),
+            "id: 2\nname: Bench, Press\nnote: paused\n"
+        );
+    }
+}
@@ -0,0 +1,278 @@
+use crate::error::{CliError, CliResult};
+use api::ExerciseManagement;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::widgets::{List, ListItem, ListState, Paragraph};
+use ratatui::{DefaultTerminal, Frame, TerminalOptions, Viewport};
+use std::io::IsTerminal;
+
+/// Most exercises offered by the picker, well above what a training log holds.
+pub const PICKER_LIMIT: u32 = 1000;
+
+/// Lines of the terminal the picker takes up below the prompt.
+const PICKER_HEIGHT: u16 = 10;
+
+/// State of the exercise picker, kept apart from the terminal so key handling can be tested.
+#[derive(Debug)]
+pub struct Picker {
+    names: Vec<String>,
+    query: String,
+    selected: usize,
+    done: bool,
+    picked: Option<String>,
+}
+
+impl Picker {
+    pub fn new(names: Vec<String>) -> Self {
+        Self {
+            names,
+            query: String::new(),
+            selected: 0,
+            done: false,
+            picked: None,
+        }
+    }
+
+    /// The names containing the characters of the query in order, ignoring case, with the most
+    /// compact matches first, e.g. `bp` ranks `BP Row` ahead of `Bench Press`.
+    pub fn matches(&self) -> Vec<&str> {
+        let mut scored: Vec<(usize, &str)> = self
+            .names
+            .iter()
+            .filter_map(|n| fuzzy_score(&self.query, n).map(|s| (s, n.as_str())))
+            .collect();
+        scored.sort_by_key(|(score, name)| (*score, name.len()));
+        scored.into_iter().map(|(_, name)| name).collect()
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    /// The name chosen with enter, `None` when the picker was cancelled.
+    pub fn picked(&self) -> Option<&str> {
+        self.picked.as_deref()
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        if key.kind != KeyEventKind::Press {
+            return;
+        }
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            self.done = true;
+            return;
+        }
+
+        match key.code {
+            KeyCode::Esc => self.done = true,
+            KeyCode::Enter => {
+                self.picked = self.matches().get(self.selected).map(|n| n.to_string());
+                self.done = self.picked.is_some();
+            }
+            KeyCode::Down => self.move_selection(1),
+            KeyCode::Up => self.move_selection(-1),
+            KeyCode::Backspace => {
+                self.query.pop();
+                self.selected = 0;
+            }
+            KeyCode::Char(c)
+                if !key
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                self.query.push(c);
+                self.selected = 0;
+            }
+            _ => {}
+        }
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let count = self.matches().len();
+        if count > 0 {
+            self.selected = self.selected.saturating_add_signed(delta).min(count - 1);
+        }
+    }
+
+    pub fn draw(&self, frame: &mut Frame) {
+        let [prompt_area, list_area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(frame.area());
+
+        let matches = self.matches();
+        frame.render_widget(
+            Paragraph::new(format!(
+                "exercise> {}  ({}/{})",
+                self.query,
+                matches.len(),
+                self.names.len()
+            )),
+            prompt_area,
+        );
+
+        let items: Vec<ListItem> = matches.iter().map(|n| ListItem::new(*n)).collect();
+        let list =
+            List::new(items).highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        let selected = (!matches.is_empty()).then_some(self.selected);
+        let mut state = ListState::default().with_selected(selected);
+        frame.render_stateful_widget(list, list_area, &mut state);
+    }
+}
+
+/// How spread out the characters of `query` are in `name`, lower is a better match.  `None` when
+/// `name` doesn't contain them in order.
+fn fuzzy_score(query: &str, name: &str) -> Option<usize> {
+    let name: Vec<char> = name.to_lowercase().chars().collect();
+    let mut first = None;
+    let mut position = 0;
+    for q in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found = position + name[position..].iter().position(|c| *c == q)?;
+        first.get_or_insert(found);
+        position = found + 1;
+    }
+    Some(first.map_or(0, |first| position - first))
+}
+
+/// Asks for an exercise with the picker when a command was run without a name.  Without a
+/// terminal to ask on, the name has to be given on the command line.
+pub async fn pick_exercise<M: ExerciseManagement + Sync>(
+    mgr: &M,
+    name: Option<String>,
+) -> CliResult<String> {
+    if let Some(name) = name {
+        return Ok(name);
+    }
+    if !std::io::stdin().is_terminal() || !std::io::stdout().is_terminal() {
+        return Err(CliError::NoExerciseSelected);
+    }
+
+    let names = mgr
+        .autocomplete(String::new(), PICKER_LIMIT)
+        .await?
+        .into_iter()
+        .map(|e| e.name)
+        .collect();
+    let mut picker = Picker::new(names);
+    let mut terminal = ratatui::try_init_with_options(TerminalOptions {
+        viewport: Viewport::Inline(PICKER_HEIGHT),
+    })?;
+    let result = event_loop(&mut terminal, &mut picker).and_then(|_| Ok(terminal.clear()?));
+    ratatui::restore();
+    result?;
+
+    picker
+        .picked()
+        .map(|n| n.to_string())
+        .ok_or(CliError::NoExerciseSelected)
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, picker: &mut Picker) -> CliResult<()> {
+    while !picker.is_done() {
+        terminal.draw(|frame| picker.draw(frame))?;
+        if let Event::Key(key) = event::read()? {
+            picker.handle_key(key);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    fn picker() -> Picker {
+        Picker::new(
+            ["Bench Press", "BP Row", "Deadlift", "Kettlebell Swing"]
+                .iter()
+                .map(|n| n.to_string())
+                .collect(),
+        )
+    }
+
+    fn press(picker: &mut Picker, codes: &[KeyCode]) {
+        for code in codes {
+            picker.handle_key(KeyEvent::from(*code));
+        }
+    }
+
+    fn type_query(picker: &mut Picker, query: &str) {
+        press(
+            picker,
+            &query.chars().map(KeyCode::Char).collect::<Vec<_>>(),
+        );
+    }
+
+    #[test]
+    fn fuzzy_score_ok() {
+        assert_eq!(fuzzy_score("", "Deadlift"), Some(0));
+        assert_eq!(fuzzy_score("dl", "Deadlift"), Some(5));
+        assert_eq!(fuzzy_score("DEAD", "deadlift"), Some(4));
+        assert_eq!(fuzzy_score("ld", "Deadlift"), None);
+    }
+
+    #[test]
+    fn matches_ranked() {
+        let mut picker = picker();
+        assert_eq!(picker.matches().len(), 4);
+        type_query(&mut picker, "bp");
+        assert_eq!(picker.matches(), vec!["BP Row", "Bench Press"]);
+        type_query(&mut picker, "x");
+        assert!(picker.matches().is_empty());
+    }
+
+    #[test]
+    fn pick() {
+        let mut picker = picker();
+        type_query(&mut picker, "e");
+        press(&mut picker, &[KeyCode::Down, KeyCode::Down, KeyCode::Up]);
+        press(&mut picker, &[KeyCode::Enter]);
+        assert!(picker.is_done());
+        assert_eq!(picker.picked(), Some("Bench Press"));
+    }
+
+    #[test]
+    fn cancel() {
+        let mut picker = picker();
+        type_query(&mut picker, "zz");
+        // Nothing to pick, so enter keeps the picker open
+        press(&mut picker, &[KeyCode::Enter]);
+        assert!(!picker.is_done());
+        press(&mut picker, &[KeyCode::Esc]);
+        assert!(picker.is_done());
+        assert_eq!(picker.picked(), None);
+    }
+
+    #[test]
+    fn query_ignores_shortcuts() {
+        let mut picker = picker();
+        type_query(&mut picker, "d");
+        picker.handle_key(KeyEvent::new(KeyCode::Char('w'), KeyModifiers::CONTROL));
+        picker.handle_key(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::ALT));
+        assert_eq!(picker.matches(), vec!["Deadlift"]);
+
+        // Shift only changes the character typed
+        picker.handle_key(KeyEvent::new(KeyCode::Char('X'), KeyModifiers::SHIFT));
+        assert!(picker.matches().is_empty());
+        assert!(!picker.is_done());
+    }
+
+    #[test]
+    fn draw() {
+        let mut picker = picker();
+        type_query(&mut picker, "sw");
+        let mut terminal = Terminal::new(TestBackend::new(40, 5)).unwrap();
+        terminal.draw(|frame| picker.draw(frame)).unwrap();
+
+        let rendered: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(rendered.contains("exercise> sw  (1/4)"));
+        assert!(rendered.contains("Kettlebell Swing"));
+    }
+}
@@ -0,0 +1,220 @@
+use crate::cli::Cli;
+use crate::config::{Config, Units};
+use crate::error::{CliError, CliResult};
+use sqlite::QueryOptions;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Resolved runtime configuration shared by every command.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Settings {
+    pub profile: String,
+    pub database: Option<PathBuf>,
+    pub remote_url: Option<String>,
+    pub units: Units,
+    pub locale: String,
+    pub query_options: QueryOptions,
+}
+
+impl Settings {
+    /// Builds the settings from the configuration file and the active profile, with any values
+    /// given on the command line taking precedence.
+    pub fn from_cli(cli: &Cli) -> CliResult<Self> {
+        let config = match config_path(cli) {
+            Some(path) => Config::load(&path)?,
+            None => Config::default(),
+        };
+        Self::from_config(&config, cli.profile.as_deref(), cli.database.clone())
+    }
+
+    /// Selects `profile` (or the configured default) from `config`.  Explicitly requesting a
+    /// profile that does not exist is an error, while a missing default profile is treated as
+    /// empty so a fresh install works with just `--database`.
+    pub fn from_config(
+        config: &Config,
+        profile: Option<&str>,
+        database: Option<PathBuf>,
+    ) -> CliResult<Self> {
+        let name = profile.unwrap_or_else(|| config.active_profile_name());
+        let values = match config.profiles.get(name) {
+            Some(p) => p.clone(),
+            None if profile.is_none() => Default::default(),
+            None => return Err(CliError::UnknownProfile(name.to_string())),
+        };
+
+        Ok(Self {
+            profile: name.to_string(),
+            database: database.or(values.database),
+            remote_url: values.remote_url,
+            units: values.units.unwrap_or_default(),
+            locale: values
+                .locale
+                .unwrap_or_else(|| i18n::FALLBACK_LOCALE.to_string()),
+            query_options: QueryOptions {
+                timeout: values.query_timeout_ms.map(Duration::from_millis),
+                slow_threshold: values.slow_query_ms.map(Duration::from_millis),
+            },
+        })
+    }
+
+    /// The sqlite database of the profile, required by every command that reads or writes data.
+    /// A local profile without one uses [`sqlite::default_db_path`]; a remote profile has no
+    /// database unless one is configured.
+    pub fn database(&self) -> CliResult<PathBuf> {
+        let default = match self.remote_url {
+            None => sqlite::default_db_path(),
+            Some(_) => None,
+        };
+        self.database
+            .clone()
+            .or(default)
+            .ok_or_else(|| CliError::MissingDatabase(self.profile.clone()))
+    }
+}
+
+/// The configuration file given with `--config`, otherwise the platform default.
+pub fn config_path(cli: &Cli) -> Option<PathBuf> {
+    cli.config.clone().or_else(Config::default_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Profile;
+    use clap::Parser;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn config() -> Config {
+        let mut config = Config::default();
+        config.profiles.insert(
+            "home".to_string(),
+            Profile {
+                database: Some(PathBuf::from("/data/home.db3")),
+                units: Some(Units::Lb),
+                slow_query_ms: Some(500),
+                ..Default::default()
+            },
+        );
+        config.profiles.insert(
+            "remote".to_string(),
+            Profile {
+                remote_url: Some("https://trainer.example.com".to_string()),
+                ..Default::default()
+            },
+        );
+        config
+    }
+
+    #[test]
+    fn from_cli_with_database() {
+        let dir = tempdir().unwrap();
+        let config = dir.path().join("config.toml");
+        let cli = Cli::parse_from([
+            "trainer",
+            "--config",
+            config.to_str().unwrap(),
+            "--database",
+            "/tmp/trainer.db3",
+            "exercise",
+            "list",
+        ]);
+        let settings = Settings::from_cli(&cli).unwrap();
+        assert_eq!(
+            settings.database().unwrap(),
+            PathBuf::from("/tmp/trainer.db3")
+        );
+        assert_eq!(settings.units, Units::Kg);
+    }
+
+    #[test]
+    fn from_cli_with_config_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(
+            &path,
+            "default_profile = \"home\"\n[profiles.home]\ndatabase = \"/data/home.db3\"\n",
+        )
+        .unwrap();
+        let cli = Cli::parse_from([
+            "trainer",
+            "--config",
+            path.to_str().unwrap(),
+            "exercise",
+            "list",
+        ]);
+        let settings = Settings::from_cli(&cli).unwrap();
+        assert_eq!(settings.profile, "home");
+        assert_eq!(
+            settings.database().unwrap(),
+            PathBuf::from("/data/home.db3")
+        );
+    }
+
+    #[test]
+    fn from_config_named_profile() {
+        let settings = Settings::from_config(&config(), Some("home"), None).unwrap();
+        assert_eq!(settings.profile, "home");
+        assert_eq!(
+            settings.database().unwrap(),
+            PathBuf::from("/data/home.db3")
+        );
+        assert_eq!(settings.units, Units::Lb);
+        assert_eq!(settings.remote_url, None);
+        assert_eq!(
+            settings.query_options,
+            QueryOptions {
+                timeout: None,
+                slow_threshold: Some(Duration::from_millis(500)),
+            }
+        );
+    }
+
+    #[test]
+    fn from_config_database_override() {
+        let settings = Settings::from_config(
+            &config(),
+            Some("home"),
+            Some(PathBuf::from("/tmp/other.db3")),
+        )
+        .unwrap();
+        assert_eq!(
+            settings.database().unwrap(),
+            PathBuf::from("/tmp/other.db3")
+        );
+    }
+
+    #[test]
+    fn from_config_default_profile() {
+        let mut config = config();
+        config.default_profile = Some("remote".to_string());
+        let settings = Settings::from_config(&config, None, None).unwrap();
+        assert_eq!(settings.profile, "remote");
+        assert_eq!(
+            settings.remote_url,
+            Some("https://trainer.example.com".to_string())
+        );
+        assert!(matches!(
+            settings.database().err().unwrap(),
+            CliError::MissingDatabase(p) if p == "remote"
+        ))
+    }
+
+    #[test]
+    fn from_config_missing_default_profile() {
+        let settings = Settings::from_config(&Config::default(), None, None).unwrap();
+        assert_eq!(settings.profile, "default");
+        assert_eq!(settings.locale, "en");
+        assert_eq!(settings.database, None);
+        assert_eq!(settings.database().ok(), sqlite::default_db_path());
+    }
+
+    #[test]
+    fn from_config_unknown_profile() {
+        let result = Settings::from_config(&config(), Some("work"), None);
+        assert!(matches!(
+            result.err().unwrap(),
+            CliError::UnknownProfile(p) if p == "work"
+        ))
+    }
+}
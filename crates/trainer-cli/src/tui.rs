@@ -0,0 +1,301 @@
+use crate::error::{CliError, CliResult};
+use api::{Exercise, ExerciseManagement};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use std::io::IsTerminal;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Mode {
+    Browse,
+    Search,
+}
+
+/// State of the terminal dashboard, kept apart from the terminal so key handling can be tested.
+#[derive(Debug)]
+pub struct App {
+    exercises: Vec<Exercise>,
+    filter: String,
+    selected: usize,
+    mode: Mode,
+    quit: bool,
+}
+
+impl App {
+    pub fn new(exercises: Vec<Exercise>) -> Self {
+        Self {
+            exercises,
+            filter: String::new(),
+            selected: 0,
+            mode: Mode::Browse,
+            quit: false,
+        }
+    }
+
+    /// The exercises whose name contains the search text, ignoring case.
+    pub fn visible(&self) -> Vec<&Exercise> {
+        let filter = self.filter.to_lowercase();
+        self.exercises
+            .iter()
+            .filter(|e| e.name.to_lowercase().contains(&filter))
+            .collect()
+    }
+
+    pub fn selected(&self) -> Option<&Exercise> {
+        self.visible().get(self.selected).copied()
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    pub fn should_quit(&self) -> bool {
+        self.quit
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        if key.kind != KeyEventKind::Press {
+            return;
+        }
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            self.quit = true;
+            return;
+        }
+
+        match (self.mode, key.code) {
+            (Mode::Browse, KeyCode::Char('q')) => self.quit = true,
+            (Mode::Browse, KeyCode::Char('/')) => self.mode = Mode::Search,
+            (Mode::Browse, KeyCode::Esc) => self.set_filter(String::new()),
+            (Mode::Browse, KeyCode::Char('j')) | (_, KeyCode::Down) => self.move_selection(1),
+            (Mode::Browse, KeyCode::Char('k')) | (_, KeyCode::Up) => self.move_selection(-1),
+            (Mode::Search, KeyCode::Enter) => self.mode = Mode::Browse,
+            (Mode::Search, KeyCode::Esc) => {
+                self.set_filter(String::new());
+                self.mode = Mode::Browse;
+            }
+            (Mode::Search, KeyCode::Backspace) => {
+                let mut filter = self.filter.clone();
+                filter.pop();
+                self.set_filter(filter);
+            }
+            (Mode::Search, KeyCode::Char(c))
+                if !key
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                self.set_filter(format!("{}{}", self.filter, c))
+            }
+            _ => {}
+        }
+    }
+
+    fn set_filter(&mut self, filter: String) {
+        self.filter = filter;
+        self.selected = 0;
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let count = self.visible().len();
+        if count > 0 {
+            self.selected = self.selected.saturating_add_signed(delta).min(count - 1);
+        }
+    }
+
+    pub fn draw(&self, frame: &mut Frame) {
+        let [search_area, main_area, help_area] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+        let [list_area, details_area] =
+            Layout::horizontal([Constraint::Percentage(40), Constraint::Percentage(60)])
+                .areas(main_area);
+
+        let search_style = match self.mode {
+            Mode::Search => Style::default().add_modifier(Modifier::BOLD),
+            Mode::Browse => Style::default(),
+        };
+        frame.render_widget(
+            Paragraph::new(self.filter.as_str())
+                .style(search_style)
+                .block(Block::bordered().title("Search")),
+            search_area,
+        );
+
+        let visible = self.visible();
+        let items: Vec<ListItem> = visible
+            .iter()
+            .map(|e| ListItem::new(e.name.as_str()))
+            .collect();
+        let title = format!("Exercises ({})", visible.len());
+        let list = List::new(items)
+            .block(Block::bordered().title(title))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        let mut state = ListState::default().with_selected(self.selected().map(|_| self.selected));
+        frame.render_stateful_widget(list, list_area, &mut state);
+
+        let details = match self.selected() {
+            Some(e) => vec![
+                Line::from(format!("Name:        {}", e.name)),
+                Line::from(format!("Type:        {}", e.exercise_type)),
+                Line::from(format!(
+                    "Description: {}",
+                    e.description.as_deref().unwrap_or_default()
+                )),
+            ],
+            None => vec![Line::from("No exercises match")],
+        };
+        frame.render_widget(
+            Paragraph::new(details)
+                .wrap(Wrap { trim: true })
+                .block(Block::bordered().title("Details")),
+            details_area,
+        );
+
+        let help = match self.mode {
+            Mode::Browse => "/ search  ↑↓/jk select  esc clear  q quit",
+            Mode::Search => "type to filter  enter done  esc clear",
+        };
+        frame.render_widget(Paragraph::new(help), help_area);
+    }
+}
+
+/// Runs the dashboard until the user quits.  The terminal is restored even when reading events
+/// fails.  Without a terminal to draw on it fails with [`CliError::NoTerminal`].
+pub async fn run<M: ExerciseManagement + Sync>(mgr: &M) -> CliResult<()> {
+    if !std::io::stdin().is_terminal() || !std::io::stdout().is_terminal() {
+        return Err(CliError::NoTerminal);
+    }
+
+    let mut app = App::new(mgr.list().await?);
+    let mut terminal = ratatui::try_init()?;
+    let result = event_loop(&mut terminal, &mut app);
+    ratatui::restore();
+    result
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, app: &mut App) -> CliResult<()> {
+    while !app.should_quit() {
+        terminal.draw(|frame| app.draw(frame))?;
+        if let Event::Key(key) = event::read()? {
+            app.handle_key(key);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use api::ExerciseType;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    fn sample_app() -> App {
+        let exercise = |name: &str, exercise_type| Exercise {
+            id: None,
+            name: name.to_string(),
+            description: None,
+            exercise_type,
+            bar_weight: None,
+            load_increment: None,
+        };
+        App::new(vec![
+            exercise("Bench Press", ExerciseType::Barbell),
+            exercise("Deadlift", ExerciseType::Barbell),
+            exercise("Swing", ExerciseType::KettleBell),
+        ])
+    }
+
+    fn press(app: &mut App, codes: &[KeyCode]) {
+        for code in codes {
+            app.handle_key(KeyEvent::from(*code));
+        }
+    }
+
+    #[test]
+    fn navigate() {
+        let mut app = sample_app();
+        assert_eq!(app.selected().unwrap().name, "Bench Press");
+        press(
+            &mut app,
+            &[KeyCode::Char('j'), KeyCode::Down, KeyCode::Down],
+        );
+        assert_eq!(app.selected().unwrap().name, "Swing");
+        press(&mut app, &[KeyCode::Char('k')]);
+        assert_eq!(app.selected().unwrap().name, "Deadlift");
+        press(&mut app, &[KeyCode::Up, KeyCode::Up]);
+        assert_eq!(app.selected().unwrap().name, "Bench Press");
+    }
+
+    #[test]
+    fn search() {
+        let mut app = sample_app();
+        press(&mut app, &[KeyCode::Char('/')]);
+        assert_eq!(app.mode(), Mode::Search);
+
+        // Keys that navigate while browsing are search text while searching
+        press(
+            &mut app,
+            &"DEq".chars().map(KeyCode::Char).collect::<Vec<_>>(),
+        );
+        assert!(app.visible().is_empty());
+        assert!(!app.should_quit());
+
+        press(&mut app, &[KeyCode::Backspace, KeyCode::Enter]);
+        assert_eq!(app.mode(), Mode::Browse);
+        assert_eq!(app.visible().len(), 1);
+        assert_eq!(app.selected().unwrap().name, "Deadlift");
+
+        press(&mut app, &[KeyCode::Esc]);
+        assert_eq!(app.visible().len(), 3);
+    }
+
+    #[test]
+    fn search_ignores_shortcuts() {
+        let mut app = sample_app();
+        press(&mut app, &[KeyCode::Char('/'), KeyCode::Char('s')]);
+        app.handle_key(KeyEvent::new(KeyCode::Char('w'), KeyModifiers::CONTROL));
+        app.handle_key(KeyEvent::new(KeyCode::Char('b'), KeyModifiers::ALT));
+        assert_eq!(app.visible().len(), 2);
+        assert_eq!(app.selected().unwrap().name, "Bench Press");
+
+        // Shift only changes the character typed
+        app.handle_key(KeyEvent::new(KeyCode::Char('W'), KeyModifiers::SHIFT));
+        assert_eq!(app.selected().unwrap().name, "Swing");
+    }
+
+    #[test]
+    fn quit() {
+        let mut app = sample_app();
+        press(&mut app, &[KeyCode::Char('q')]);
+        assert!(app.should_quit());
+
+        let mut app = sample_app();
+        app.handle_key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL));
+        assert!(app.should_quit());
+    }
+
+    #[test]
+    fn draw() {
+        let mut app = sample_app();
+        press(&mut app, &[KeyCode::Down]);
+        let mut terminal = Terminal::new(TestBackend::new(60, 10)).unwrap();
+        terminal.draw(|frame| app.draw(frame)).unwrap();
+
+        let rendered: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(rendered.contains("Exercises (3)"));
+        assert!(rendered.contains("Name:        Deadlift"));
+        assert!(rendered.contains("Type:        Barbell"));
+    }
+}
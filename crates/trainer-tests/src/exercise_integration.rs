@@ -24,6 +24,8 @@ mod exercise_tests {
             name: "Deadlift".to_string(),
             description: None,
             exercise_type: Barbell,
+            bar_weight: None,
+            load_increment: None,
         }
     }
 
@@ -33,6 +35,8 @@ mod exercise_tests {
             name: "Benchpress".to_string(),
             description: None,
             exercise_type: Barbell,
+            bar_weight: None,
+            load_increment: None,
         }
     }
 
@@ -42,6 +46,8 @@ mod exercise_tests {
             name: "Squat".to_string(),
             description: None,
             exercise_type: Barbell,
+            bar_weight: None,
+            load_increment: None,
         }
     }
 
@@ -56,7 +62,7 @@ mod exercise_tests {
         let mut dl = deadlift(None);
         let create_result = mgr.save(&mut dl).await;
         assert!(create_result.is_ok());
-        assert!(matches!(dl.id, Some(_)));
+        assert!(dl.id.is_some());
     }
 
     #[test(tokio::test)]
@@ -75,7 +81,7 @@ mod exercise_tests {
         for exercise in exercises {
             let create_result = mgr.save(exercise).await;
             assert!(create_result.is_ok());
-            assert!(matches!(exercise.id, Some(_)));
+            assert!(exercise.id.is_some());
         }
     }
 }
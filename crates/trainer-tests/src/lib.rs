@@ -1 +1,2 @@
 mod exercise_integration;
+mod workout_integration;
@@ -0,0 +1,147 @@
+#[cfg(test)]
+mod workout_tests {
+    use api::exercise::ExerciseType::Barbell;
+    use api::{
+        Exercise, ExerciseManagement, ExerciseManager, NaiveDate, Set, Weight, WorkoutError,
+        WorkoutManagement, WorkoutManager, WorkoutSession,
+    };
+    use rand::distributions::Alphanumeric;
+    use rand::{thread_rng, Rng};
+    use sqlite::{DBType, SqliteExerciseRepository};
+    use std::collections::BTreeMap;
+    use tempfile::tempdir;
+    use test_log::test;
+
+    fn db_name() -> String {
+        let rand_string: String = thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(10)
+            .map(char::from)
+            .collect();
+
+        format!("testdb-{}.db3", rand_string)
+    }
+
+    fn exercise(name: &str) -> Exercise {
+        Exercise {
+            id: None,
+            name: name.to_string(),
+            description: None,
+            exercise_type: Barbell,
+            bar_weight: None,
+            load_increment: None,
+        }
+    }
+
+    fn set(session_id: i64, exercise_id: i64, weight: f64) -> Set {
+        Set {
+            id: None,
+            session_id,
+            exercise_id,
+            set_number: 0,
+            weight: Some(Weight::kg(weight)),
+            reps: 5,
+            rpe: None,
+        }
+    }
+
+    #[test(tokio::test)]
+    async fn record_sets_during_workout() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join(db_name());
+        let repo = SqliteExerciseRepository::new(DBType::File(file_path.as_path()))
+            .await
+            .unwrap();
+        let exercises = ExerciseManager::new(&repo).unwrap();
+        let mut squat = exercise("Squat");
+        let mut deadlift = exercise("Deadlift");
+        exercises.save(&mut squat).await.unwrap();
+        exercises.save(&mut deadlift).await.unwrap();
+        let (squat, deadlift) = (squat.id.unwrap(), deadlift.id.unwrap());
+
+        let mgr = WorkoutManager::new(&repo).unwrap();
+        let mut session = WorkoutSession {
+            id: None,
+            date: NaiveDate::from_ymd_opt(2024, 5, 1).unwrap(),
+            name: Some("Heavy day".to_string()),
+            notes: None,
+            exercises: vec![],
+            metrics: BTreeMap::new(),
+        };
+        mgr.save_session(&mut session).await.unwrap();
+        let session_id = session.id.unwrap();
+
+        for (exercise_id, weight) in [(squat, 140.0), (squat, 142.5), (deadlift, 180.0)] {
+            mgr.record_set(&mut set(session_id, exercise_id, weight))
+                .await
+                .unwrap();
+        }
+
+        let sets = mgr.sets(session_id).await.unwrap();
+        assert_eq!(
+            sets.iter()
+                .map(|s| (s.exercise_id, s.set_number))
+                .collect::<Vec<_>>(),
+            vec![(squat, 1), (squat, 2), (deadlift, 1)]
+        );
+        let session = mgr.get_session(session_id).await.unwrap();
+        assert_eq!(
+            session
+                .exercises
+                .iter()
+                .map(|e| e.exercise_id)
+                .collect::<Vec<_>>(),
+            vec![squat, deadlift]
+        );
+
+        let mut corrected = sets[1].clone();
+        corrected.reps = 4;
+        mgr.update_set(&corrected).await.unwrap();
+        mgr.delete_set(sets[0].id.unwrap()).await.unwrap();
+        assert_eq!(
+            mgr.sets(session_id).await.unwrap(),
+            vec![corrected, sets[2].clone()]
+        );
+    }
+
+    #[test(tokio::test)]
+    async fn record_set_unknown_session() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join(db_name());
+        let repo = SqliteExerciseRepository::new(DBType::File(file_path.as_path()))
+            .await
+            .unwrap();
+        let mgr = WorkoutManager::new(&repo).unwrap();
+
+        let result = mgr.record_set(&mut set(1000, 1, 100.0)).await;
+        assert!(matches!(
+            result.err().unwrap(),
+            WorkoutError::SessionNotFoundError
+        ));
+    }
+
+    #[test(tokio::test)]
+    async fn record_set_unknown_exercise_leaves_session_unchanged() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join(db_name());
+        let repo = SqliteExerciseRepository::new(DBType::File(file_path.as_path()))
+            .await
+            .unwrap();
+        let mgr = WorkoutManager::new(&repo).unwrap();
+        let mut session = WorkoutSession {
+            id: None,
+            date: NaiveDate::from_ymd_opt(2024, 5, 1).unwrap(),
+            name: None,
+            notes: None,
+            exercises: vec![],
+            metrics: BTreeMap::new(),
+        };
+        mgr.save_session(&mut session).await.unwrap();
+        let session_id = session.id.unwrap();
+
+        let result = mgr.record_set(&mut set(session_id, 1000, 100.0)).await;
+        assert!(matches!(result.err().unwrap(), WorkoutError::SaveFailed));
+        assert_eq!(mgr.get_session(session_id).await.unwrap(), session);
+        assert!(mgr.sets(session_id).await.unwrap().is_empty());
+    }
+}
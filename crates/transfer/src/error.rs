@@ -0,0 +1,65 @@
+use api::{ExerciseError, TrainerErrorCode};
+
+pub type TransferResult<T, E = TransferError> = Result<T, E>;
+
+#[derive(thiserror::Error, Debug)]
+#[non_exhaustive]
+pub enum TransferError {
+    #[error("MissingColumnError: {0}")]
+    MissingColumnError(String),
+
+    #[error("InvalidMappingError: {0}")]
+    InvalidMappingError(String),
+
+    #[error("CsvError: {0}")]
+    CsvError(String),
+
+    #[error("JsonError: {0}")]
+    JsonError(String),
+
+    #[error("ExerciseError: {0}")]
+    ExerciseError(#[from] ExerciseError),
+
+    #[error("IoError: {0}")]
+    IoError(#[from] std::io::Error),
+}
+
+impl TransferError {
+    pub fn code(&self) -> TrainerErrorCode {
+        match self {
+            TransferError::MissingColumnError(_) => TrainerErrorCode::MissingColumn,
+            TransferError::InvalidMappingError(_) => TrainerErrorCode::InvalidMapping,
+            TransferError::CsvError(_) => TrainerErrorCode::InvalidCsv,
+            TransferError::JsonError(_) => TrainerErrorCode::InvalidJson,
+            TransferError::ExerciseError(e) => e.code(),
+            TransferError::IoError(_) => TrainerErrorCode::IoError,
+        }
+    }
+}
+
+impl From<csv::Error> for TransferError {
+    fn from(value: csv::Error) -> Self {
+        TransferError::CsvError(value.to_string())
+    }
+}
+
+impl From<serde_json::Error> for TransferError {
+    fn from(value: serde_json::Error) -> Self {
+        TransferError::JsonError(value.to_string())
+    }
+}
+
+/// A row of an import file that could not be used.  Row errors are collected rather than failing
+/// the whole import so the caller can report every problem at once.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RowError {
+    /// 1-based line number in the source file
+    pub line: u64,
+    pub message: String,
+}
+
+impl std::fmt::Display for RowError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
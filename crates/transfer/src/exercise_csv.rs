@@ -0,0 +1,361 @@
+use crate::{RowError, TransferError, TransferResult};
+use api::{Exercise, ExerciseType, Weight};
+use std::collections::HashSet;
+use std::io::{Read, Write};
+use tracing::{debug, instrument};
+
+/// Header names used by [`write_exercises`] and expected by default by [`read_exercises`].
+pub const NAME_COLUMN: &str = "name";
+pub const TYPE_COLUMN: &str = "exercise_type";
+pub const DESCRIPTION_COLUMN: &str = "description";
+pub const BAR_WEIGHT_COLUMN: &str = "bar_weight";
+pub const LOAD_INCREMENT_COLUMN: &str = "load_increment";
+
+/// Maps the fields of an [`Exercise`] to the column headers of an import file, so files produced
+/// by other tools can be imported without editing them first.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColumnMapping {
+    pub name: String,
+    pub exercise_type: String,
+    pub description: String,
+
+    /// Used when the type column is absent or empty for a row
+    pub default_type: Option<ExerciseType>,
+}
+
+impl Default for ColumnMapping {
+    fn default() -> Self {
+        Self {
+            name: NAME_COLUMN.to_string(),
+            exercise_type: TYPE_COLUMN.to_string(),
+            description: DESCRIPTION_COLUMN.to_string(),
+            default_type: None,
+        }
+    }
+}
+
+/// The result of reading an import file.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ParsedExercises {
+    pub exercises: Vec<Exercise>,
+    pub errors: Vec<RowError>,
+
+    /// Whether the source has descriptions at all, e.g. a description column.  Without them an
+    /// existing exercise keeps its description, while an empty one clears it
+    pub has_descriptions: bool,
+
+    /// Whether the source only names the exercises, e.g. a workout log.  Such an import creates
+    /// the exercises that are missing and leaves existing ones as they are
+    pub create_only: bool,
+}
+
+/// Writes `exercises` as CSV using the default column names.  Repository identifiers are not
+/// exported as they are meaningless in another database.
+#[instrument(skip_all, fields(count = exercises.len()))]
+pub fn write_exercises<W: Write>(out: W, exercises: &[Exercise]) -> TransferResult<()> {
+    let mut writer = csv::Writer::from_writer(out);
+    writer.write_record([
+        NAME_COLUMN,
+        TYPE_COLUMN,
+        DESCRIPTION_COLUMN,
+        BAR_WEIGHT_COLUMN,
+        LOAD_INCREMENT_COLUMN,
+    ])?;
+    let weight = |w: Option<Weight>| w.map(|w| w.to_string()).unwrap_or_default();
+    for e in exercises {
+        writer.write_record([
+            e.name.as_str(),
+            e.exercise_type.to_string().to_lowercase().as_str(),
+            e.description.as_deref().unwrap_or_default(),
+            weight(e.bar_weight).as_str(),
+            weight(e.load_increment).as_str(),
+        ])?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Reads exercises from CSV.  Headers are matched case-insensitively against `mapping`; the name
+/// column is required, while the type column may be omitted when `mapping.default_type` is set.
+/// The optional `bar_weight` and `load_increment` columns hold weights such as `20kg` or `5lb`.
+/// Rows that can't be converted are reported in [`ParsedExercises::errors`].
+#[instrument(skip_all)]
+pub fn read_exercises<R: Read>(
+    input: R,
+    mapping: &ColumnMapping,
+) -> TransferResult<ParsedExercises> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(input);
+
+    let headers = reader.headers()?.clone();
+    let find = |column: &str| {
+        headers
+            .iter()
+            .position(|h| h.eq_ignore_ascii_case(column.trim()))
+    };
+
+    let name_idx = find(&mapping.name)
+        .ok_or_else(|| TransferError::MissingColumnError(mapping.name.clone()))?;
+    let type_idx = find(&mapping.exercise_type);
+    if type_idx.is_none() && mapping.default_type.is_none() {
+        return Err(TransferError::MissingColumnError(
+            mapping.exercise_type.clone(),
+        ));
+    }
+    let description_idx = find(&mapping.description);
+    let bar_weight_idx = find(BAR_WEIGHT_COLUMN);
+    let load_increment_idx = find(LOAD_INCREMENT_COLUMN);
+
+    let mut parsed = ParsedExercises {
+        has_descriptions: description_idx.is_some(),
+        ..Default::default()
+    };
+    let mut seen = HashSet::new();
+    for record in reader.records() {
+        let record = match record {
+            Ok(r) => r,
+            Err(e) => {
+                let line = e.position().map(|p| p.line()).unwrap_or_default();
+                parsed.errors.push(RowError {
+                    line,
+                    message: e.to_string(),
+                });
+                continue;
+            }
+        };
+        let line = record.position().map(|p| p.line()).unwrap_or_default();
+        let field = |idx: Option<usize>| {
+            idx.and_then(|i| record.get(i))
+                .filter(|v| !v.is_empty())
+                .map(|v| v.to_string())
+        };
+
+        let Some(name) = field(Some(name_idx)) else {
+            parsed.errors.push(RowError {
+                line,
+                message: "missing exercise name".to_string(),
+            });
+            continue;
+        };
+
+        let exercise_type = match field(type_idx) {
+            Some(value) => match value.parse::<ExerciseType>() {
+                Ok(et) => et,
+                Err(e) => {
+                    parsed.errors.push(RowError {
+                        line,
+                        message: e.to_string(),
+                    });
+                    continue;
+                }
+            },
+            None => match mapping.default_type {
+                Some(et) => et,
+                None => {
+                    parsed.errors.push(RowError {
+                        line,
+                        message: format!("missing exercise type for '{}'", name),
+                    });
+                    continue;
+                }
+            },
+        };
+
+        let weights = [bar_weight_idx, load_increment_idx]
+            .map(|idx| field(idx).map(|v| v.parse::<Weight>()).transpose());
+        let [bar_weight, load_increment] = match weights {
+            [Ok(bar_weight), Ok(load_increment)] => [bar_weight, load_increment],
+            [Err(e), _] | [_, Err(e)] => {
+                parsed.errors.push(RowError {
+                    line,
+                    message: e.to_string(),
+                });
+                continue;
+            }
+        };
+
+        if !seen.insert(name.to_lowercase()) {
+            parsed.errors.push(RowError {
+                line,
+                message: format!("duplicate exercise '{}'", name),
+            });
+            continue;
+        }
+
+        parsed.exercises.push(Exercise {
+            id: None,
+            name,
+            description: field(description_idx),
+            exercise_type,
+            bar_weight,
+            load_increment,
+        });
+    }
+
+    debug!(
+        "parsed {} exercises with {} errors",
+        parsed.exercises.len(),
+        parsed.errors.len()
+    );
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use api::ExerciseType::{Barbell, BodyWeight, KettleBell};
+
+    fn exercise(name: &str, et: ExerciseType, description: Option<&str>) -> Exercise {
+        Exercise {
+            id: None,
+            name: name.to_string(),
+            description: description.map(|d| d.to_string()),
+            exercise_type: et,
+            bar_weight: None,
+            load_increment: None,
+        }
+    }
+
+    #[test]
+    fn write_ok() {
+        let exercises = vec![
+            Exercise {
+                id: Some(10),
+                bar_weight: Some(Weight::kg(20.0)),
+                load_increment: Some(Weight::kg(2.5)),
+                ..exercise("Deadlift", Barbell, Some("From the floor, to the hips"))
+            },
+            exercise("Swing", KettleBell, None),
+        ];
+        let mut out = Vec::new();
+        write_exercises(&mut out, &exercises).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "name,exercise_type,description,bar_weight,load_increment\nDeadlift,barbell,\"From the floor, to the hips\",20kg,2.5kg\nSwing,kettlebell,,,\n"
+        );
+    }
+
+    #[test]
+    fn round_trip_ok() {
+        let exercises = vec![
+            Exercise {
+                load_increment: Some(Weight::lb(5.0)),
+                ..exercise("Deadlift", Barbell, Some("From the floor"))
+            },
+            exercise("Swing", KettleBell, None),
+            exercise("Pull Up", BodyWeight, None),
+        ];
+        let mut out = Vec::new();
+        write_exercises(&mut out, &exercises).unwrap();
+
+        let parsed = read_exercises(out.as_slice(), &ColumnMapping::default()).unwrap();
+        assert!(parsed.errors.is_empty());
+        assert_eq!(parsed.exercises, exercises);
+    }
+
+    #[test]
+    fn read_with_mapping() {
+        let input = "Exercise,Category,Notes\nBench Press,BB,Paused\nDips,,\n";
+        let mapping = ColumnMapping {
+            name: "exercise".to_string(),
+            exercise_type: "Category".to_string(),
+            description: "notes".to_string(),
+            default_type: Some(BodyWeight),
+        };
+        let parsed = read_exercises(input.as_bytes(), &mapping).unwrap();
+        assert!(parsed.errors.is_empty());
+        assert!(parsed.has_descriptions);
+        assert_eq!(
+            parsed.exercises,
+            vec![
+                exercise("Bench Press", Barbell, Some("Paused")),
+                exercise("Dips", BodyWeight, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn read_without_type_column_uses_default() {
+        let input = "name\nPush Up\n";
+        let mapping = ColumnMapping {
+            default_type: Some(BodyWeight),
+            ..Default::default()
+        };
+        let parsed = read_exercises(input.as_bytes(), &mapping).unwrap();
+        assert_eq!(
+            parsed.exercises,
+            vec![exercise("Push Up", BodyWeight, None)]
+        );
+        assert!(!parsed.has_descriptions);
+    }
+
+    #[test]
+    fn read_invalid_weight() {
+        let input = "name,exercise_type,bar_weight\nSquat,barbell,20\nPress,barbell,15 kg\n";
+        let parsed = read_exercises(input.as_bytes(), &ColumnMapping::default()).unwrap();
+        assert_eq!(
+            parsed.exercises,
+            vec![Exercise {
+                bar_weight: Some(Weight::kg(15.0)),
+                ..exercise("Press", Barbell, None)
+            }]
+        );
+        assert_eq!(
+            parsed.errors,
+            vec![RowError {
+                line: 2,
+                message: "invalid weight, expected e.g. 20kg or 45lb: 20".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn read_missing_name_column() {
+        let input = "title,exercise_type\nSquat,barbell\n";
+        let result = read_exercises(input.as_bytes(), &ColumnMapping::default());
+        assert!(matches!(
+            result.err().unwrap(),
+            TransferError::MissingColumnError(c) if c == "name"
+        ))
+    }
+
+    #[test]
+    fn read_missing_type_column() {
+        let input = "name\nSquat\n";
+        let result = read_exercises(input.as_bytes(), &ColumnMapping::default());
+        assert!(matches!(
+            result.err().unwrap(),
+            TransferError::MissingColumnError(c) if c == "exercise_type"
+        ))
+    }
+
+    #[test]
+    fn read_collects_row_errors() {
+        let input = "name,exercise_type\nSquat,barbell\n,barbell\nBand Pull Apart,band\nRow,\nsquat,barbell\n";
+        let parsed = read_exercises(input.as_bytes(), &ColumnMapping::default()).unwrap();
+        assert_eq!(parsed.exercises, vec![exercise("Squat", Barbell, None)]);
+        assert_eq!(
+            parsed.errors,
+            vec![
+                RowError {
+                    line: 3,
+                    message: "missing exercise name".to_string()
+                },
+                RowError {
+                    line: 4,
+                    message: "unsupported exercise type: band".to_string()
+                },
+                RowError {
+                    line: 5,
+                    message: "missing exercise type for 'Row'".to_string()
+                },
+                RowError {
+                    line: 6,
+                    message: "duplicate exercise 'squat'".to_string()
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,186 @@
+use crate::workout::{column, number, ImportedSet, ParsedWorkouts, WorkoutImporter, KG_PER_LB};
+use crate::{RowError, TransferError, TransferResult};
+use api::WeightUnit;
+use std::io::Read;
+use tracing::{debug, instrument};
+
+const DATE_COLUMN: &str = "date";
+const EXERCISE_COLUMN: &str = "exercise";
+const WEIGHT_UNIT_COLUMN: &str = "weight unit";
+const REPS_COLUMN: &str = "reps";
+
+/// Reads the CSV exported from FitNotes (Settings > Spreadsheet Export), which has one row per
+/// set.  FitNotes records the muscle group rather than the equipment of an exercise, so types
+/// have to come from a [`crate::names::NameMapping`] or a default.
+///
+/// The weight column is named after the unit, e.g. `Weight (kgs)` or `Weight (lbs)`; newer
+/// versions write `Weight` with a separate `Weight Unit` column instead.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FitNotesImporter;
+
+impl WorkoutImporter for FitNotesImporter {
+    fn source(&self) -> &'static str {
+        "FitNotes"
+    }
+
+    #[instrument(skip_all)]
+    fn read(&self, input: &mut dyn Read) -> TransferResult<ParsedWorkouts> {
+        let mut reader = csv::ReaderBuilder::new()
+            .flexible(true)
+            .trim(csv::Trim::All)
+            .from_reader(input);
+
+        let headers = reader.headers()?.clone();
+        let required = |name: &str| {
+            column(&headers, name).ok_or_else(|| TransferError::MissingColumnError(name.into()))
+        };
+        let date_idx = required(DATE_COLUMN)?;
+        let exercise_idx = required(EXERCISE_COLUMN)?;
+        let reps_idx = column(&headers, REPS_COLUMN);
+        let unit_idx = column(&headers, WEIGHT_UNIT_COLUMN);
+        let weight_idx = headers.iter().position(|h| {
+            let h = h.to_lowercase();
+            h.starts_with("weight") && h != WEIGHT_UNIT_COLUMN
+        });
+        let header_in_lbs = weight_idx
+            .and_then(|i| headers.get(i))
+            .is_some_and(|h| h.to_lowercase().contains("lb"));
+
+        let mut parsed = ParsedWorkouts::default();
+        for record in reader.records() {
+            let record = match record {
+                Ok(r) => r,
+                Err(e) => {
+                    let line = e.position().map(|p| p.line()).unwrap_or_default();
+                    parsed.errors.push(RowError {
+                        line,
+                        message: e.to_string(),
+                    });
+                    continue;
+                }
+            };
+            let line = record.position().map(|p| p.line()).unwrap_or_default();
+            let field =
+                |idx: Option<usize>| idx.and_then(|i| record.get(i)).filter(|v| !v.is_empty());
+
+            let Some(exercise) = field(Some(exercise_idx)) else {
+                parsed.errors.push(RowError {
+                    line,
+                    message: "missing exercise name".to_string(),
+                });
+                continue;
+            };
+            let Some(performed_at) = field(Some(date_idx)) else {
+                parsed.errors.push(RowError {
+                    line,
+                    message: format!("missing date for '{}'", exercise),
+                });
+                continue;
+            };
+
+            let values = number::<f64>(field(weight_idx), "weight")
+                .and_then(|weight| Ok((weight, number::<u32>(field(reps_idx), "reps")?)));
+            let (weight, reps) = match values {
+                Ok(v) => v,
+                Err(message) => {
+                    parsed.errors.push(RowError { line, message });
+                    continue;
+                }
+            };
+            let in_lbs = match field(unit_idx) {
+                Some(unit) => unit.to_lowercase().starts_with("lb"),
+                None => header_in_lbs,
+            };
+
+            parsed.sets.push(ImportedSet {
+                line,
+                performed_at: performed_at.to_string(),
+                workout: None,
+                exercise: exercise.to_string(),
+                exercise_type: None,
+                weight_kg: weight.map(|w| if in_lbs { w * KG_PER_LB } else { w }),
+                weight_unit: if in_lbs {
+                    WeightUnit::Lb
+                } else {
+                    WeightUnit::Kg
+                },
+                reps,
+            });
+        }
+
+        debug!(
+            "parsed {} sets with {} errors",
+            parsed.sets.len(),
+            parsed.errors.len()
+        );
+        Ok(parsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_ok() {
+        let input =
+            "Date,Exercise,Category,Weight (kgs),Reps,Distance,Distance Unit,Time,Comment\n\
+                     2024-05-01,Deadlift,Back,140.0,5,,,,\n\
+                     2024-05-01,Deadlift,Back,140.0,5,,,,Easy\n\
+                     2024-05-01,Plank,Abs,,,,,0:01:00,\n\
+                     2024-05-01,Squat,Legs,100,five,,,,\n\
+                     ,Squat,Legs,100,5,,,,\n";
+        let parsed = FitNotesImporter.read(&mut input.as_bytes()).unwrap();
+        assert_eq!(
+            parsed.sets[0],
+            ImportedSet {
+                line: 2,
+                performed_at: "2024-05-01".to_string(),
+                workout: None,
+                exercise: "Deadlift".to_string(),
+                exercise_type: None,
+                weight_kg: Some(140.0),
+                weight_unit: WeightUnit::Kg,
+                reps: Some(5),
+            }
+        );
+        assert_eq!(parsed.sets.len(), 3);
+        assert_eq!(parsed.sets[2].reps, None);
+        assert_eq!(
+            parsed
+                .errors
+                .iter()
+                .map(|e| e.to_string())
+                .collect::<Vec<_>>(),
+            vec![
+                "line 5: invalid reps 'five'",
+                "line 6: missing date for 'Squat'"
+            ]
+        );
+    }
+
+    #[test]
+    fn read_pounds() {
+        let input = "Date,Exercise,Category,Weight (lbs),Reps\n2024-05-01,Squat,Legs,225,5\n";
+        let parsed = FitNotesImporter.read(&mut input.as_bytes()).unwrap();
+        assert!((parsed.sets[0].weight_kg.unwrap() - 102.058).abs() < 0.001);
+
+        let input = "Date,Exercise,Category,Weight,Weight Unit,Reps\n\
+                     2024-05-01,Squat,Legs,225,lbs,5\n\
+                     2024-05-01,Squat,Legs,100,kgs,5\n";
+        let parsed = FitNotesImporter.read(&mut input.as_bytes()).unwrap();
+        assert!((parsed.sets[0].weight_kg.unwrap() - 102.058).abs() < 0.001);
+        assert_eq!(parsed.sets[1].weight_kg, Some(100.0));
+        assert_eq!(parsed.converted(), 1);
+    }
+
+    #[test]
+    fn read_missing_column() {
+        let input = "Day,Exercise\n2024-05-01,Squat\n";
+        let result = FitNotesImporter.read(&mut input.as_bytes());
+        assert!(matches!(
+            result.err().unwrap(),
+            TransferError::MissingColumnError(c) if c == "date"
+        ))
+    }
+}
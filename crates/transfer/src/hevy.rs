@@ -0,0 +1,197 @@
+use crate::workout::{column, number, ImportedSet, ParsedWorkouts, WorkoutImporter, KG_PER_LB};
+use crate::{RowError, TransferError, TransferResult};
+use api::{ExerciseType, WeightUnit};
+use std::io::Read;
+use tracing::{debug, instrument};
+
+const TITLE_COLUMN: &str = "title";
+const START_COLUMN: &str = "start_time";
+const EXERCISE_COLUMN: &str = "exercise_title";
+const WEIGHT_KG_COLUMN: &str = "weight_kg";
+const WEIGHT_LBS_COLUMN: &str = "weight_lbs";
+const REPS_COLUMN: &str = "reps";
+
+/// Reads the workout CSV exported from the Hevy app (Settings > Export & Import Data), which has
+/// one row per set.  Exports in either kilograms or pounds are accepted.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct HevyImporter;
+
+impl WorkoutImporter for HevyImporter {
+    fn source(&self) -> &'static str {
+        "Hevy"
+    }
+
+    #[instrument(skip_all)]
+    fn read(&self, input: &mut dyn Read) -> TransferResult<ParsedWorkouts> {
+        let mut reader = csv::ReaderBuilder::new()
+            .flexible(true)
+            .trim(csv::Trim::All)
+            .from_reader(input);
+
+        let headers = reader.headers()?.clone();
+        let required = |name: &str| {
+            column(&headers, name).ok_or_else(|| TransferError::MissingColumnError(name.into()))
+        };
+        let start_idx = required(START_COLUMN)?;
+        let exercise_idx = required(EXERCISE_COLUMN)?;
+        let title_idx = column(&headers, TITLE_COLUMN);
+        let reps_idx = column(&headers, REPS_COLUMN);
+        let (weight_idx, weight_unit) = match column(&headers, WEIGHT_KG_COLUMN) {
+            Some(idx) => (Some(idx), WeightUnit::Kg),
+            None => (column(&headers, WEIGHT_LBS_COLUMN), WeightUnit::Lb),
+        };
+        let weight_factor = match weight_unit {
+            WeightUnit::Kg => 1.0,
+            WeightUnit::Lb => KG_PER_LB,
+        };
+
+        let mut parsed = ParsedWorkouts::default();
+        for record in reader.records() {
+            let record = match record {
+                Ok(r) => r,
+                Err(e) => {
+                    let line = e.position().map(|p| p.line()).unwrap_or_default();
+                    parsed.errors.push(RowError {
+                        line,
+                        message: e.to_string(),
+                    });
+                    continue;
+                }
+            };
+            let line = record.position().map(|p| p.line()).unwrap_or_default();
+            let field =
+                |idx: Option<usize>| idx.and_then(|i| record.get(i)).filter(|v| !v.is_empty());
+
+            let Some(exercise) = field(Some(exercise_idx)) else {
+                parsed.errors.push(RowError {
+                    line,
+                    message: "missing exercise name".to_string(),
+                });
+                continue;
+            };
+            let Some(performed_at) = field(Some(start_idx)) else {
+                parsed.errors.push(RowError {
+                    line,
+                    message: format!("missing start time for '{}'", exercise),
+                });
+                continue;
+            };
+
+            let values = number::<f64>(field(weight_idx), "weight")
+                .and_then(|weight| Ok((weight, number::<u32>(field(reps_idx), "reps")?)));
+            let (weight, reps) = match values {
+                Ok(v) => v,
+                Err(message) => {
+                    parsed.errors.push(RowError { line, message });
+                    continue;
+                }
+            };
+
+            parsed.sets.push(ImportedSet {
+                line,
+                performed_at: performed_at.to_string(),
+                workout: field(title_idx).map(|t| t.to_string()),
+                exercise: exercise.to_string(),
+                exercise_type: equipment(exercise),
+                weight_kg: weight.map(|w| w * weight_factor),
+                weight_unit,
+                reps,
+            });
+        }
+
+        debug!(
+            "parsed {} sets with {} errors",
+            parsed.sets.len(),
+            parsed.errors.len()
+        );
+        Ok(parsed)
+    }
+}
+
+/// Hevy names most exercises with the equipment in parentheses, e.g. "Squat (Barbell)".
+fn equipment(exercise: &str) -> Option<ExerciseType> {
+    let (_, suffix) = exercise.strip_suffix(')')?.rsplit_once('(')?;
+    match suffix.trim().to_lowercase().as_str() {
+        "barbell" => Some(ExerciseType::Barbell),
+        "kettlebell" => Some(ExerciseType::KettleBell),
+        "bodyweight" => Some(ExerciseType::BodyWeight),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use api::ExerciseType::{Barbell, KettleBell};
+
+    const EXPORT: &str = r#""title","start_time","end_time","description","exercise_title","superset_id","exercise_notes","set_index","set_type","weight_kg","reps","distance_km","duration_seconds","rpe"
+"Morning","1 May 2024, 07:00","1 May 2024, 08:00","","Deadlift (Barbell)",,"",0,"normal",140,5,,,8
+"Morning","1 May 2024, 07:00","1 May 2024, 08:00","","Kettlebell Swing",,"",0,"normal",24,10,,,
+"Morning","1 May 2024, 07:00","1 May 2024, 08:00","","Pull Up",,"",0,"normal",,8,,,
+"Morning","1 May 2024, 07:00","1 May 2024, 08:00","","Goblet Squat (Kettlebell)",,"",0,"normal",heavy,8,,,
+"Morning","1 May 2024, 07:00","1 May 2024, 08:00","","",,"",0,"normal",20,8,,,
+"#;
+
+    #[test]
+    fn read_ok() {
+        let parsed = HevyImporter.read(&mut EXPORT.as_bytes()).unwrap();
+        assert_eq!(parsed.sets.len(), 3);
+        assert_eq!(
+            parsed.sets[0],
+            ImportedSet {
+                line: 2,
+                performed_at: "1 May 2024, 07:00".to_string(),
+                workout: Some("Morning".to_string()),
+                exercise: "Deadlift (Barbell)".to_string(),
+                exercise_type: Some(Barbell),
+                weight_kg: Some(140.0),
+                weight_unit: WeightUnit::Kg,
+                reps: Some(5),
+            }
+        );
+        assert_eq!(parsed.sets[1].exercise_type, None);
+        assert_eq!(parsed.sets[2].weight_kg, None);
+        assert_eq!(
+            parsed.errors,
+            vec![
+                RowError {
+                    line: 5,
+                    message: "invalid weight 'heavy'".to_string()
+                },
+                RowError {
+                    line: 6,
+                    message: "missing exercise name".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn read_pounds() {
+        let input =
+            "title,start_time,exercise_title,weight_lbs,reps\nA,2024-05-01,Squat (Barbell),225,5\n";
+        let parsed = HevyImporter.read(&mut input.as_bytes()).unwrap();
+        let weight = parsed.sets[0].weight_kg.unwrap();
+        assert!((weight - 102.058).abs() < 0.001);
+        assert_eq!(parsed.sets[0].weight_unit, WeightUnit::Lb);
+        assert_eq!(parsed.converted(), 1);
+    }
+
+    #[test]
+    fn read_missing_column() {
+        let input = "title,start_time,weight_kg\nA,2024-05-01,100\n";
+        let result = HevyImporter.read(&mut input.as_bytes());
+        assert!(matches!(
+            result.err().unwrap(),
+            TransferError::MissingColumnError(c) if c == "exercise_title"
+        ))
+    }
+
+    #[test]
+    fn equipment_ok() {
+        assert_eq!(equipment("Bench Press (Barbell)"), Some(Barbell));
+        assert_eq!(equipment("Goblet Squat (kettlebell)"), Some(KettleBell));
+        assert_eq!(equipment("Bench Press (Dumbbell)"), None);
+        assert_eq!(equipment("Pull Up"), None);
+    }
+}
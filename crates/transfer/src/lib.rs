@@ -0,0 +1,12 @@
+mod error;
+pub mod exercise_csv;
+pub mod fitnotes;
+pub mod hevy;
+pub mod library;
+pub mod matcher;
+pub mod names;
+pub mod plan;
+pub mod wger;
+pub mod workout;
+
+pub use self::error::*;
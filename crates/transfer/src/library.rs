@@ -0,0 +1,33 @@
+use crate::exercise_csv::{read_exercises, ColumnMapping, ParsedExercises};
+
+/// The standard exercise library in the CSV format of [`read_exercises`].
+const LIBRARY: &str = include_str!("../library/exercises.csv");
+
+/// Common barbell, kettlebell and bodyweight exercises for seeding a new database, so a first
+/// workout can be logged without typing in every exercise.
+pub fn standard_library() -> ParsedExercises {
+    read_exercises(LIBRARY.as_bytes(), &ColumnMapping::default())
+        .expect("the standard library is valid CSV")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use api::ExerciseType;
+
+    #[test]
+    fn standard_library_ok() {
+        let library = standard_library();
+        assert!(library.errors.is_empty(), "{:?}", library.errors);
+        for exercise_type in [
+            ExerciseType::Barbell,
+            ExerciseType::KettleBell,
+            ExerciseType::BodyWeight,
+        ] {
+            assert!(library
+                .exercises
+                .iter()
+                .any(|e| e.exercise_type == exercise_type));
+        }
+    }
+}
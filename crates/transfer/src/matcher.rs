@@ -0,0 +1,195 @@
+use api::{Exercise, ExerciseAlias};
+use std::collections::HashMap;
+
+/// Fuzzy matches scoring below this are taken to be a different exercise.
+pub const MIN_CONFIDENCE: f64 = 0.85;
+
+/// An existing exercise a free-text name was resolved to.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Resolved<'a> {
+    pub exercise: &'a Exercise,
+
+    /// From 0 to 1, where 1 is an exact match of the normalized name or of an alias
+    pub confidence: f64,
+}
+
+/// Resolves the exercise names found in imports against the existing exercises and their
+/// aliases, so e.g. "Squat (Barbell)" and "barbell squats" both find "Barbell Squat".
+#[derive(Clone, Debug)]
+pub struct ExerciseMatcher<'a> {
+    names: Vec<(String, &'a Exercise)>,
+    exact: HashMap<String, &'a Exercise>,
+}
+
+impl<'a> ExerciseMatcher<'a> {
+    /// Aliases pointing to an exercise missing from `exercises` are ignored.
+    pub fn new(exercises: &'a [Exercise], aliases: &[ExerciseAlias]) -> Self {
+        let by_id: HashMap<i64, &Exercise> = exercises
+            .iter()
+            .filter_map(|e| e.id.map(|id| (id, e)))
+            .collect();
+
+        let mut names: Vec<(String, &Exercise)> =
+            exercises.iter().map(|e| (normalize(&e.name), e)).collect();
+        names.extend(
+            aliases
+                .iter()
+                .filter_map(|a| by_id.get(&a.exercise_id).map(|e| (normalize(&a.alias), *e))),
+        );
+
+        // Exercise names win over aliases that normalize to the same text
+        let mut exact = HashMap::new();
+        for (name, exercise) in &names {
+            exact.entry(name.clone()).or_insert(*exercise);
+        }
+        Self { names, exact }
+    }
+
+    /// The existing exercise `name` most likely refers to, or `None` when nothing scores at
+    /// least [`MIN_CONFIDENCE`].  Only a confidence of 1 says it is the same exercise, anything
+    /// less is a guess for the user to confirm: one changed letter turns "Incline Bench Press"
+    /// into "Decline Bench Press".
+    pub fn resolve(&self, name: &str) -> Option<Resolved<'a>> {
+        let normalized = normalize(name);
+        if let Some(exercise) = self.exact.get(&normalized) {
+            return Some(Resolved {
+                exercise,
+                confidence: 1.0,
+            });
+        }
+
+        self.names
+            .iter()
+            .map(|(candidate, exercise)| Resolved {
+                exercise,
+                confidence: strsim::normalized_damerau_levenshtein(&normalized, candidate),
+            })
+            .filter(|r| r.confidence >= MIN_CONFIDENCE)
+            .max_by(|a, b| a.confidence.total_cmp(&b.confidence))
+    }
+}
+
+/// Reduces a name to the words that identify the exercise: lowercase, without punctuation, with
+/// plurals made singular and the words sorted, so "Pull-Ups" and "pull up" compare equal.
+pub fn normalize(name: &str) -> String {
+    let lower = name.to_lowercase();
+    let mut words: Vec<&str> = lower
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| match w.strip_suffix('s') {
+            Some(singular) if singular.len() > 1 && !singular.ends_with('s') => singular,
+            _ => w,
+        })
+        .collect();
+    words.sort_unstable();
+    words.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use api::ExerciseType::{self, Barbell, BodyWeight};
+
+    fn exercise(id: i64, name: &str, exercise_type: ExerciseType) -> Exercise {
+        Exercise {
+            id: Some(id),
+            name: name.to_string(),
+            description: None,
+            exercise_type,
+            bar_weight: None,
+            load_increment: None,
+        }
+    }
+
+    fn exercises() -> Vec<Exercise> {
+        vec![
+            exercise(1, "Barbell Squat", Barbell),
+            exercise(2, "Bench Press", Barbell),
+            exercise(3, "Pull Up", BodyWeight),
+            exercise(4, "Deadlift", Barbell),
+        ]
+    }
+
+    fn resolved<'a>(matcher: &ExerciseMatcher<'a>, name: &str) -> Option<(&'a str, f64)> {
+        matcher
+            .resolve(name)
+            .map(|r| (r.exercise.name.as_str(), r.confidence))
+    }
+
+    #[test]
+    fn normalize_ok() {
+        assert_eq!(normalize("Squat (Barbell)"), "barbell squat");
+        assert_eq!(normalize("  Pull-Ups "), "pull up");
+        assert_eq!(normalize("Bench Press"), "bench press");
+        assert_eq!(normalize("Press"), "press");
+        assert_eq!(normalize(""), "");
+    }
+
+    #[test]
+    fn resolve_exact() {
+        let exercises = exercises();
+        let matcher = ExerciseMatcher::new(&exercises, &[]);
+        assert_eq!(
+            resolved(&matcher, "bench press"),
+            Some(("Bench Press", 1.0))
+        );
+        assert_eq!(
+            resolved(&matcher, "Squat (Barbell)"),
+            Some(("Barbell Squat", 1.0))
+        );
+        assert_eq!(resolved(&matcher, "pull-ups"), Some(("Pull Up", 1.0)));
+    }
+
+    #[test]
+    fn resolve_fuzzy() {
+        let exercises = exercises();
+        let matcher = ExerciseMatcher::new(&exercises, &[]);
+
+        let (name, confidence) = resolved(&matcher, "Dead lift").unwrap();
+        assert_eq!(name, "Deadlift");
+        assert!((MIN_CONFIDENCE..1.0).contains(&confidence));
+
+        let (name, _) = resolved(&matcher, "Benchpress").unwrap();
+        assert_eq!(name, "Bench Press");
+
+        assert_eq!(resolved(&matcher, "Overhead Press"), None);
+        assert_eq!(resolved(&matcher, "Row"), None);
+    }
+
+    #[test]
+    fn resolve_similar_names_is_not_exact() {
+        let exercises = vec![exercise(1, "Incline Bench Press", Barbell)];
+        let matcher = ExerciseMatcher::new(&exercises, &[]);
+        let (name, confidence) = resolved(&matcher, "Decline Bench Press").unwrap();
+        assert_eq!(name, "Incline Bench Press");
+        assert!(confidence < 1.0);
+    }
+
+    #[test]
+    fn resolve_alias() {
+        let exercises = exercises();
+        let aliases = vec![
+            ExerciseAlias {
+                alias: "Chin Up".to_string(),
+                exercise_id: 3,
+            },
+            // Aliases of deleted exercises are ignored
+            ExerciseAlias {
+                alias: "Curl".to_string(),
+                exercise_id: 100,
+            },
+            // The exercise name wins over an alias with the same normalized name
+            ExerciseAlias {
+                alias: "deadlifts".to_string(),
+                exercise_id: 1,
+            },
+        ];
+        let matcher = ExerciseMatcher::new(&exercises, &aliases);
+        assert_eq!(resolved(&matcher, "chin-ups"), Some(("Pull Up", 1.0)));
+        assert_eq!(resolved(&matcher, "Curl"), None);
+        assert_eq!(resolved(&matcher, "Deadlift"), Some(("Deadlift", 1.0)));
+
+        let (name, _) = resolved(&matcher, "Chin-Upz").unwrap();
+        assert_eq!(name, "Pull Up");
+    }
+}
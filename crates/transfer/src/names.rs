@@ -0,0 +1,154 @@
+use crate::{TransferError, TransferResult};
+use api::ExerciseType;
+use std::collections::HashMap;
+use std::io::Read;
+use tracing::{debug, instrument};
+
+pub const SOURCE_COLUMN: &str = "source";
+pub const NAME_COLUMN: &str = "name";
+pub const TYPE_COLUMN: &str = "exercise_type";
+
+/// The trainer exercise a name from another app is imported as.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MappedName {
+    pub name: String,
+    pub exercise_type: Option<ExerciseType>,
+}
+
+/// Renames exercises from other apps to their trainer names, e.g. Hevy's "Deadlift (Barbell)" to
+/// "Deadlift".  Several source names may map to the same exercise.
+///
+/// The mapping file is CSV with `source` and `name` columns and an optional `exercise_type`
+/// column:
+///
+/// ```text
+/// source,name,exercise_type
+/// Deadlift (Barbell),Deadlift,
+/// Kettlebell Swing,Swing,kettlebell
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NameMapping {
+    names: HashMap<String, MappedName>,
+}
+
+impl NameMapping {
+    /// Reads a mapping file.  Unlike import files a mapping file is small and written by hand, so
+    /// any invalid row fails the whole read.
+    #[instrument(skip_all)]
+    pub fn read<R: Read>(input: R) -> TransferResult<Self> {
+        let mut reader = csv::ReaderBuilder::new()
+            .flexible(true)
+            .trim(csv::Trim::All)
+            .from_reader(input);
+
+        let headers = reader.headers()?.clone();
+        let find = |column: &str| headers.iter().position(|h| h.eq_ignore_ascii_case(column));
+        let source_idx =
+            find(SOURCE_COLUMN).ok_or(TransferError::MissingColumnError(SOURCE_COLUMN.into()))?;
+        let name_idx =
+            find(NAME_COLUMN).ok_or(TransferError::MissingColumnError(NAME_COLUMN.into()))?;
+        let type_idx = find(TYPE_COLUMN);
+
+        let mut mapping = Self::default();
+        for record in reader.records() {
+            let record = record?;
+            let line = record.position().map(|p| p.line()).unwrap_or_default();
+            let field =
+                |idx: Option<usize>| idx.and_then(|i| record.get(i)).filter(|v| !v.is_empty());
+
+            let (Some(source), Some(name)) = (field(Some(source_idx)), field(Some(name_idx)))
+            else {
+                return Err(TransferError::InvalidMappingError(format!(
+                    "line {}: source and name are required",
+                    line
+                )));
+            };
+            let exercise_type = field(type_idx)
+                .map(|v| v.parse::<ExerciseType>())
+                .transpose()
+                .map_err(|e| TransferError::InvalidMappingError(format!("line {}: {}", line, e)))?;
+            mapping.insert(source, name, exercise_type);
+        }
+
+        debug!("read {} exercise names", mapping.names.len());
+        Ok(mapping)
+    }
+
+    pub fn insert(&mut self, source: &str, name: &str, exercise_type: Option<ExerciseType>) {
+        self.names.insert(
+            source.to_lowercase(),
+            MappedName {
+                name: name.to_string(),
+                exercise_type,
+            },
+        );
+    }
+
+    /// The mapping for `source`, matched case-insensitively.
+    pub fn get(&self, source: &str) -> Option<&MappedName> {
+        self.names.get(&source.trim().to_lowercase())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use api::ExerciseType::KettleBell;
+
+    #[test]
+    fn read_ok() {
+        let input =
+            "Source,Name,Exercise_Type\nDeadlift (Barbell),Deadlift,\nKettlebell Swing,Swing,kb\n";
+        let mapping = NameMapping::read(input.as_bytes()).unwrap();
+        assert_eq!(
+            mapping.get("deadlift (barbell)"),
+            Some(&MappedName {
+                name: "Deadlift".to_string(),
+                exercise_type: None
+            })
+        );
+        assert_eq!(
+            mapping.get("Kettlebell Swing"),
+            Some(&MappedName {
+                name: "Swing".to_string(),
+                exercise_type: Some(KettleBell)
+            })
+        );
+        assert_eq!(mapping.get("Squat"), None);
+    }
+
+    #[test]
+    fn read_without_type_column() {
+        let input = "source,name\nBench Press (Barbell),Bench Press\n";
+        let mapping = NameMapping::read(input.as_bytes()).unwrap();
+        assert_eq!(
+            mapping.get("Bench Press (Barbell)").unwrap().name,
+            "Bench Press"
+        );
+    }
+
+    #[test]
+    fn read_invalid_rows() {
+        let result = NameMapping::read("source,name\nDeadlift (Barbell),\n".as_bytes());
+        assert!(matches!(
+            result.err().unwrap(),
+            TransferError::InvalidMappingError(m) if m == "line 2: source and name are required"
+        ));
+
+        let result =
+            NameMapping::read("source,name,exercise_type\nBand Pull,Pull,band\n".as_bytes());
+        assert!(matches!(
+            result.err().unwrap(),
+            TransferError::InvalidMappingError(m) if m == "line 2: unsupported exercise type: band"
+        ));
+    }
+
+    #[test]
+    fn read_missing_column() {
+        let result = NameMapping::read("from,to\na,b\n".as_bytes());
+        assert!(matches!(
+            result.err().unwrap(),
+            TransferError::MissingColumnError(c) if c == "source"
+        ))
+    }
+}
@@ -0,0 +1,580 @@
+use crate::exercise_csv::ParsedExercises;
+use crate::matcher::{normalize, ExerciseMatcher};
+use crate::{RowError, TransferResult};
+use api::{Exercise, ExerciseError, ExerciseManagement};
+use std::collections::{HashMap, HashSet};
+use tracing::{debug, instrument, warn};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Action {
+    Create,
+    Update,
+    Unchanged,
+    Skip,
+}
+
+impl std::fmt::Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            Action::Create => "create",
+            Action::Update => "update",
+            Action::Unchanged => "unchanged",
+            Action::Skip => "skip",
+        };
+        f.pad(label)
+    }
+}
+
+/// How an imported name was matched to an existing exercise.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NameMatch {
+    /// The name as it appeared in the import
+    pub imported: String,
+
+    /// From 0 to 1, see [`crate::matcher::Resolved::confidence`]
+    pub confidence: f64,
+}
+
+/// An existing exercise with a name close to an imported one.  Close isn't the same: "Decline
+/// Bench Press" is one letter away from "Incline Bench Press", so the import creates the exercise
+/// and leaves it to the user to record an alias when both are the same.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Suggestion {
+    /// The name of the existing exercise
+    pub existing: String,
+
+    /// From 0 to 1, see [`crate::matcher::Resolved::confidence`]
+    pub confidence: f64,
+}
+
+/// Why an imported exercise that would otherwise be created is left out.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SkipReason {
+    /// A deleted exercise, named as given, has the same name.  It has to be restored first
+    Deleted(String),
+
+    /// The same name appears earlier in the import
+    Duplicate,
+}
+
+/// What importing a single exercise will do.  For updates and unchanged exercises `exercise`
+/// carries the identifier and name of the existing exercise, and `matched` how it was found.
+/// Creates may carry a `suggestion` of an existing exercise that could be the same one, skips
+/// their `skip_reason`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlannedChange {
+    pub action: Action,
+    pub exercise: Exercise,
+    pub matched: Option<NameMatch>,
+    pub suggestion: Option<Suggestion>,
+    pub skip_reason: Option<SkipReason>,
+}
+
+/// The changes an import will make, computed up front so it can be reported without touching the
+/// repository (dry-run) or applied as is.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ImportPlan {
+    pub changes: Vec<PlannedChange>,
+    pub errors: Vec<RowError>,
+}
+
+impl ImportPlan {
+    /// Matches the parsed exercises against the existing ones with `matcher`.  Only exact matches
+    /// of the normalized name or of an alias are applied, fuzzy ones become suggestions.  Names
+    /// of `deleted` exercises, and names repeated in the import, are skipped as they can't be
+    /// created again.  A source without descriptions keeps those of the existing exercises, and a
+    /// [`ParsedExercises::create_only`] one leaves them unchanged altogether.
+    pub fn new(parsed: ParsedExercises, matcher: &ExerciseMatcher, deleted: &[Exercise]) -> Self {
+        let has_descriptions = parsed.has_descriptions;
+        let create_only = parsed.create_only;
+        let mut changes: Vec<PlannedChange> = parsed
+            .exercises
+            .into_iter()
+            .map(|imported| match matcher.resolve(&imported.name) {
+                None => PlannedChange {
+                    action: Action::Create,
+                    exercise: imported,
+                    matched: None,
+                    suggestion: None,
+                    skip_reason: None,
+                },
+                Some(resolved) if resolved.confidence < 1.0 => PlannedChange {
+                    action: Action::Create,
+                    exercise: imported,
+                    matched: None,
+                    suggestion: Some(Suggestion {
+                        existing: resolved.exercise.name.clone(),
+                        confidence: resolved.confidence,
+                    }),
+                    skip_reason: None,
+                },
+                Some(resolved) => {
+                    let current = resolved.exercise;
+                    // Keep what the source doesn't know about: most have no loading rules and
+                    // some no descriptions
+                    let imported = Exercise {
+                        description: match has_descriptions {
+                            true => imported.description,
+                            false => current.description.clone(),
+                        },
+                        bar_weight: imported.bar_weight.or(current.bar_weight),
+                        load_increment: imported.load_increment.or(current.load_increment),
+                        ..imported
+                    };
+                    let action = if create_only
+                        || (current.exercise_type == imported.exercise_type
+                            && current.description == imported.description
+                            && current.bar_weight == imported.bar_weight
+                            && current.load_increment == imported.load_increment)
+                    {
+                        Action::Unchanged
+                    } else {
+                        Action::Update
+                    };
+                    PlannedChange {
+                        action,
+                        matched: Some(NameMatch {
+                            imported: imported.name.clone(),
+                            confidence: resolved.confidence,
+                        }),
+                        suggestion: None,
+                        skip_reason: None,
+                        exercise: match action {
+                            Action::Unchanged => current.clone(),
+                            _ => Exercise {
+                                id: current.id,
+                                name: current.name.clone(),
+                                ..imported
+                            },
+                        },
+                    }
+                }
+            })
+            .collect();
+
+        let deleted: HashMap<String, &str> = deleted
+            .iter()
+            .map(|e| (normalize(&e.name), e.name.as_str()))
+            .collect();
+        let mut created = HashSet::new();
+        for change in changes.iter_mut().filter(|c| c.action == Action::Create) {
+            let name = normalize(&change.exercise.name);
+            change.skip_reason = match deleted.get(&name) {
+                Some(deleted) => Some(SkipReason::Deleted(deleted.to_string())),
+                None if !created.insert(name) => Some(SkipReason::Duplicate),
+                None => None,
+            };
+            if change.skip_reason.is_some() {
+                change.action = Action::Skip;
+            }
+        }
+
+        Self {
+            changes,
+            errors: parsed.errors,
+        }
+    }
+
+    pub fn count(&self, action: Action) -> usize {
+        self.changes.iter().filter(|c| c.action == action).count()
+    }
+}
+
+/// A create or update that the repository refused.
+#[derive(Clone, Debug)]
+pub struct FailedChange {
+    pub name: String,
+    pub error: ExerciseError,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ImportSummary {
+    pub created: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+    pub skipped: usize,
+    pub failed: Vec<FailedChange>,
+}
+
+/// Saves every create and update of `plan` through the exercise manager.  A change that fails
+/// is recorded in [`ImportSummary::failed`] and the remaining ones are still applied, so a
+/// single bad row doesn't leave the import half done without saying which rows made it.
+#[instrument(skip_all, fields(changes = plan.changes.len()))]
+pub async fn apply<M: ExerciseManagement + Sync>(
+    mgr: &M,
+    plan: &ImportPlan,
+) -> TransferResult<ImportSummary> {
+    let mut summary = ImportSummary {
+        skipped: plan.errors.len(),
+        ..Default::default()
+    };
+
+    for change in &plan.changes {
+        let count = match change.action {
+            Action::Create => &mut summary.created,
+            Action::Update => &mut summary.updated,
+            Action::Unchanged => {
+                summary.unchanged += 1;
+                continue;
+            }
+            Action::Skip => {
+                summary.skipped += 1;
+                continue;
+            }
+        };
+        match mgr.save(&mut change.exercise.clone()).await {
+            Ok(_) => *count += 1,
+            Err(error) => {
+                warn!("unable to import {}: {}", change.exercise.name, error);
+                summary.failed.push(FailedChange {
+                    name: change.exercise.name.clone(),
+                    error,
+                });
+            }
+        }
+    }
+
+    debug!("{:?}", summary);
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use api::ExerciseManager;
+    use api::ExerciseType::{self, Barbell, BodyWeight, KettleBell};
+    use api::{ExerciseAlias, Weight};
+    use rand::distributions::Alphanumeric;
+    use rand::{thread_rng, Rng};
+    use sqlite::{DBType, SqliteExerciseRepository};
+    use tempfile::tempdir;
+    use test_log::test;
+
+    fn db_name() -> String {
+        let rand_string: String = thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(10)
+            .map(char::from)
+            .collect();
+
+        format!("testdb-{}.db3", rand_string)
+    }
+
+    fn exercise(
+        id: Option<i64>,
+        name: &str,
+        et: ExerciseType,
+        description: Option<&str>,
+    ) -> Exercise {
+        Exercise {
+            id,
+            name: name.to_string(),
+            description: description.map(|d| d.to_string()),
+            exercise_type: et,
+            bar_weight: None,
+            load_increment: None,
+        }
+    }
+
+    fn parsed() -> ParsedExercises {
+        ParsedExercises {
+            exercises: vec![
+                exercise(None, "deadlift", Barbell, None),
+                exercise(None, "Squat", Barbell, Some("High bar")),
+                exercise(None, "Swing", KettleBell, None),
+            ],
+            errors: vec![RowError {
+                line: 5,
+                message: "missing exercise name".to_string(),
+            }],
+            has_descriptions: true,
+            create_only: false,
+        }
+    }
+
+    #[test]
+    fn plan_ok() {
+        let existing = vec![
+            exercise(Some(1), "Deadlift", Barbell, None),
+            exercise(Some(2), "Squat", Barbell, None),
+        ];
+        let plan = ImportPlan::new(parsed(), &ExerciseMatcher::new(&existing, &[]), &[]);
+
+        assert_eq!(
+            plan.changes,
+            vec![
+                PlannedChange {
+                    action: Action::Unchanged,
+                    exercise: exercise(Some(1), "Deadlift", Barbell, None),
+                    matched: Some(NameMatch {
+                        imported: "deadlift".to_string(),
+                        confidence: 1.0,
+                    }),
+                    suggestion: None,
+                    skip_reason: None,
+                },
+                PlannedChange {
+                    action: Action::Update,
+                    exercise: exercise(Some(2), "Squat", Barbell, Some("High bar")),
+                    matched: Some(NameMatch {
+                        imported: "Squat".to_string(),
+                        confidence: 1.0,
+                    }),
+                    suggestion: None,
+                    skip_reason: None,
+                },
+                PlannedChange {
+                    action: Action::Create,
+                    exercise: exercise(None, "Swing", KettleBell, None),
+                    matched: None,
+                    suggestion: None,
+                    skip_reason: None,
+                },
+            ]
+        );
+        assert_eq!(plan.count(Action::Create), 1);
+        assert_eq!(plan.count(Action::Update), 1);
+        assert_eq!(plan.count(Action::Unchanged), 1);
+        assert_eq!(plan.errors.len(), 1);
+    }
+
+    #[test]
+    fn plan_fuzzy_and_alias_matches() {
+        let existing = vec![
+            exercise(Some(1), "Romanian Deadlift", Barbell, None),
+            exercise(Some(2), "Swing", KettleBell, None),
+        ];
+        let aliases = vec![ExerciseAlias {
+            alias: "Russian Swing".to_string(),
+            exercise_id: 2,
+        }];
+        let parsed = ParsedExercises {
+            exercises: vec![
+                exercise(None, "Romanian Dead-lift", Barbell, None),
+                exercise(None, "Russian Swings", KettleBell, None),
+            ],
+            errors: vec![],
+            has_descriptions: true,
+            create_only: false,
+        };
+        let plan = ImportPlan::new(parsed, &ExerciseMatcher::new(&existing, &aliases), &[]);
+
+        // Fuzzy matches are only suggested
+        assert_eq!(plan.changes[0].action, Action::Create);
+        assert_eq!(plan.changes[0].exercise.name, "Romanian Dead-lift");
+        assert_eq!(plan.changes[0].matched, None);
+        let suggestion = plan.changes[0].suggestion.as_ref().unwrap();
+        assert_eq!(suggestion.existing, "Romanian Deadlift");
+        assert!(suggestion.confidence < 1.0);
+
+        assert_eq!(plan.changes[1].action, Action::Unchanged);
+        assert_eq!(plan.changes[1].exercise, existing[1]);
+        assert_eq!(plan.changes[1].matched.as_ref().unwrap().confidence, 1.0);
+        assert_eq!(plan.changes[1].suggestion, None);
+    }
+
+    #[test]
+    fn plan_does_not_apply_similar_names() {
+        let existing = vec![exercise(Some(1), "Incline Bench Press", Barbell, None)];
+        let parsed = ParsedExercises {
+            exercises: vec![exercise(None, "Decline Bench Press", Barbell, None)],
+            errors: vec![],
+            has_descriptions: true,
+            create_only: false,
+        };
+        let plan = ImportPlan::new(parsed, &ExerciseMatcher::new(&existing, &[]), &[]);
+
+        assert_eq!(plan.count(Action::Create), 1);
+        assert_eq!(plan.count(Action::Update), 0);
+        assert_eq!(
+            plan.changes[0].exercise,
+            exercise(None, "Decline Bench Press", Barbell, None)
+        );
+        assert_eq!(
+            plan.changes[0].suggestion.as_ref().unwrap().existing,
+            "Incline Bench Press"
+        );
+    }
+
+    #[test]
+    fn plan_skips_deleted_and_repeated_names() {
+        let deleted = vec![exercise(Some(2), "Beta", Barbell, None)];
+        let parsed = ParsedExercises {
+            exercises: vec![
+                exercise(None, "Alpha", Barbell, None),
+                exercise(None, "beta", Barbell, None),
+                exercise(None, "Gamma", Barbell, None),
+                exercise(None, "Alphas", Barbell, None),
+            ],
+            errors: vec![],
+            has_descriptions: true,
+            create_only: false,
+        };
+        let plan = ImportPlan::new(parsed, &ExerciseMatcher::new(&[], &[]), &deleted);
+
+        let actions: Vec<_> = plan.changes.iter().map(|c| c.action).collect();
+        assert_eq!(
+            actions,
+            vec![Action::Create, Action::Skip, Action::Create, Action::Skip]
+        );
+        assert_eq!(
+            plan.changes[1].skip_reason,
+            Some(SkipReason::Deleted("Beta".to_string()))
+        );
+        assert_eq!(plan.changes[3].skip_reason, Some(SkipReason::Duplicate));
+    }
+
+    #[test]
+    fn plan_keeps_loading_rules() {
+        let current = Exercise {
+            bar_weight: Some(Weight::kg(15.0)),
+            load_increment: Some(Weight::kg(1.25)),
+            ..exercise(Some(1), "Press", Barbell, None)
+        };
+        let parsed = |imported: Exercise| ParsedExercises {
+            exercises: vec![imported],
+            errors: vec![],
+            has_descriptions: true,
+            create_only: false,
+        };
+
+        let existing = [current.clone()];
+        let matcher = ExerciseMatcher::new(&existing, &[]);
+        let plan = ImportPlan::new(
+            parsed(exercise(None, "press", Barbell, None)),
+            &matcher,
+            &[],
+        );
+        assert_eq!(plan.changes[0].action, Action::Unchanged);
+        assert_eq!(plan.changes[0].exercise, current);
+
+        let imported = Exercise {
+            load_increment: Some(Weight::kg(2.5)),
+            ..exercise(None, "press", Barbell, None)
+        };
+        let plan = ImportPlan::new(parsed(imported), &matcher, &[]);
+        assert_eq!(plan.changes[0].action, Action::Update);
+        assert_eq!(plan.changes[0].exercise.bar_weight, Some(Weight::kg(15.0)));
+        assert_eq!(
+            plan.changes[0].exercise.load_increment,
+            Some(Weight::kg(2.5))
+        );
+    }
+
+    #[test]
+    fn plan_keeps_descriptions_without_column() {
+        let existing = vec![exercise(
+            Some(1),
+            "Deadlift",
+            Barbell,
+            Some("From the floor"),
+        )];
+        let matcher = ExerciseMatcher::new(&existing, &[]);
+        let parsed = |has_descriptions| ParsedExercises {
+            exercises: vec![exercise(None, "Deadlift", Barbell, None)],
+            errors: vec![],
+            has_descriptions,
+            create_only: false,
+        };
+
+        let plan = ImportPlan::new(parsed(false), &matcher, &[]);
+        assert_eq!(plan.changes[0].action, Action::Unchanged);
+        assert_eq!(plan.changes[0].exercise, existing[0]);
+
+        // An empty description in a description column clears it
+        let plan = ImportPlan::new(parsed(true), &matcher, &[]);
+        assert_eq!(plan.changes[0].action, Action::Update);
+        assert_eq!(plan.changes[0].exercise.description, None);
+    }
+
+    #[test]
+    fn plan_create_only_leaves_existing() {
+        let existing = vec![exercise(
+            Some(1),
+            "Pull Up",
+            BodyWeight,
+            Some("Overhand grip"),
+        )];
+        let parsed = ParsedExercises {
+            exercises: vec![
+                exercise(None, "pull up", Barbell, None),
+                exercise(None, "Deadlift", Barbell, None),
+            ],
+            errors: vec![],
+            has_descriptions: false,
+            create_only: true,
+        };
+        let plan = ImportPlan::new(parsed, &ExerciseMatcher::new(&existing, &[]), &[]);
+
+        assert_eq!(plan.changes[0].action, Action::Unchanged);
+        assert_eq!(plan.changes[0].exercise, existing[0]);
+        assert_eq!(plan.changes[1].action, Action::Create);
+    }
+
+    #[test(tokio::test)]
+    async fn apply_ok() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join(db_name());
+        let repo = SqliteExerciseRepository::new(DBType::File(file_path.as_path()))
+            .await
+            .unwrap();
+        let mgr = ExerciseManager::new(&repo).unwrap();
+        mgr.save(&mut exercise(None, "Deadlift", Barbell, None))
+            .await
+            .unwrap();
+        mgr.save(&mut exercise(None, "Squat", Barbell, None))
+            .await
+            .unwrap();
+
+        let existing = mgr.list().await.unwrap();
+        let plan = ImportPlan::new(parsed(), &ExerciseMatcher::new(&existing, &[]), &[]);
+        let summary = apply(&mgr, &plan).await.unwrap();
+        assert_eq!(
+            (
+                summary.created,
+                summary.updated,
+                summary.unchanged,
+                summary.skipped
+            ),
+            (1, 1, 1, 1)
+        );
+        assert!(summary.failed.is_empty());
+
+        let squat = mgr.get_by_name("Squat".to_string()).await.unwrap();
+        assert_eq!(squat.description, Some("High bar".to_string()));
+        let swing = mgr.get_by_name("Swing".to_string()).await.unwrap();
+        assert_eq!(swing.exercise_type, KettleBell);
+        assert_eq!(mgr.list().await.unwrap().len(), 3);
+    }
+
+    #[test(tokio::test)]
+    async fn apply_continues_after_failure() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join(db_name());
+        let repo = SqliteExerciseRepository::new(DBType::File(file_path.as_path()))
+            .await
+            .unwrap();
+        let mgr = ExerciseManager::new(&repo).unwrap();
+        mgr.save(&mut exercise(None, "Beta", Barbell, None))
+            .await
+            .unwrap();
+
+        // Planned without looking at the repository, so Beta is created a second time
+        let create = |name| PlannedChange {
+            action: Action::Create,
+            exercise: exercise(None, name, Barbell, None),
+            matched: None,
+            suggestion: None,
+            skip_reason: None,
+        };
+        let plan = ImportPlan {
+            changes: vec![create("Alpha"), create("Beta"), create("Gamma")],
+            errors: vec![],
+        };
+        let summary = apply(&mgr, &plan).await.unwrap();
+        assert_eq!(summary.created, 2);
+        assert_eq!(summary.failed.len(), 1);
+        assert_eq!(summary.failed[0].name, "Beta");
+        assert_eq!(mgr.list().await.unwrap().len(), 3);
+    }
+}
@@ -0,0 +1,262 @@
+use crate::exercise_csv::ParsedExercises;
+use crate::TransferResult;
+use api::{Exercise, ExerciseType};
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::io::Read;
+use tracing::{debug, instrument};
+
+/// wger language id of English, the only language exercise names are read in.
+pub const ENGLISH: i64 = 2;
+
+/// A page of the wger `exerciseinfo` endpoint, e.g. the response of
+/// `https://wger.de/api/v2/exerciseinfo/?language=2&limit=1000` saved to a file.
+#[derive(Clone, Debug, Deserialize)]
+struct Page {
+    results: Vec<WgerExercise>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct WgerExercise {
+    id: i64,
+
+    #[serde(default)]
+    equipment: Vec<Named>,
+
+    /// Older versions of the API call the translations `exercises`
+    #[serde(default, alias = "exercises")]
+    translations: Vec<Translation>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct Named {
+    name: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct Translation {
+    name: String,
+
+    #[serde(default)]
+    description: String,
+
+    language: i64,
+}
+
+/// The exercises of a wger catalog.  Most of the catalog uses equipment trainer doesn't track
+/// (dumbbells, machines, cables), so those are counted rather than reported as errors.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Catalog {
+    pub parsed: ParsedExercises,
+
+    /// Exercises skipped for their equipment or for having no English name
+    pub unsupported: usize,
+}
+
+/// Reads a catalog page saved from the wger `exerciseinfo` endpoint.  Exercises listing no
+/// equipment are given `default_type`, or skipped when it is `None`.  Descriptions are converted
+/// from HTML to plain text.
+#[instrument(skip_all)]
+pub fn read_catalog<R: Read>(
+    input: R,
+    default_type: Option<ExerciseType>,
+) -> TransferResult<Catalog> {
+    let page: Page = serde_json::from_reader(input)?;
+
+    let mut catalog = Catalog::default();
+    catalog.parsed.has_descriptions = true;
+    let mut seen = HashSet::new();
+    for exercise in page.results {
+        let translation = exercise
+            .translations
+            .iter()
+            .find(|t| t.language == ENGLISH && !t.name.trim().is_empty());
+        let exercise_type = match exercise.equipment.is_empty() {
+            true => default_type,
+            false => equipment(&exercise.equipment),
+        };
+        let (Some(translation), Some(exercise_type)) = (translation, exercise_type) else {
+            debug!("skipping wger exercise {}", exercise.id);
+            catalog.unsupported += 1;
+            continue;
+        };
+
+        let name = translation.name.trim().to_string();
+        if !seen.insert(name.to_lowercase()) {
+            continue;
+        }
+        let description = plain_text(&translation.description);
+        catalog.parsed.exercises.push(Exercise {
+            id: None,
+            name,
+            description: (!description.is_empty()).then_some(description),
+            exercise_type,
+            bar_weight: None,
+            load_increment: None,
+        });
+    }
+
+    debug!(
+        "read {} exercises, {} unsupported",
+        catalog.parsed.exercises.len(),
+        catalog.unsupported
+    );
+    Ok(catalog)
+}
+
+/// Maps wger equipment to an [`ExerciseType`].  Barbells take precedence over kettlebells, e.g. for
+/// an exercise listing a barbell and a bench.
+fn equipment(equipment: &[Named]) -> Option<ExerciseType> {
+    let names: Vec<String> = equipment.iter().map(|e| e.name.to_lowercase()).collect();
+    let has = |candidates: &[&str]| names.iter().any(|n| candidates.contains(&n.as_str()));
+
+    if has(&["barbell", "sz-bar"]) {
+        Some(ExerciseType::Barbell)
+    } else if has(&["kettlebell"]) {
+        Some(ExerciseType::KettleBell)
+    } else if names
+        .iter()
+        .all(|n| n.starts_with("none") || ["pull-up bar", "gym mat", "bench"].contains(&n.as_str()))
+    {
+        Some(ExerciseType::BodyWeight)
+    } else {
+        None
+    }
+}
+
+/// Strips the tags of a wger description and collapses its whitespace.
+fn plain_text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => {
+                in_tag = true;
+                text.push(' ');
+            }
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TransferError;
+    use api::ExerciseType::{Barbell, BodyWeight, KettleBell};
+
+    const PAGE: &str = r#"{
+  "count": 6,
+  "next": null,
+  "previous": null,
+  "results": [
+    {
+      "id": 73,
+      "category": {"id": 11, "name": "Chest"},
+      "muscles": [{"id": 4, "name": "Pectoralis major"}],
+      "equipment": [{"id": 1, "name": "Barbell"}, {"id": 8, "name": "Bench"}],
+      "translations": [
+        {"id": 1, "name": "Bankdrücken", "description": "<p>Langhantel</p>", "language": 1},
+        {"id": 2, "name": "Bench Press", "description": "<p>Lower the bar to the chest,&nbsp;then press.</p>\n<ul><li>Keep feet flat</li></ul>", "language": 2}
+      ]
+    },
+    {
+      "id": 200,
+      "equipment": [{"id": 10, "name": "Kettlebell"}],
+      "translations": [{"id": 3, "name": "Kettlebell Swings", "description": "", "language": 2}]
+    },
+    {
+      "id": 300,
+      "equipment": [{"id": 6, "name": "Pull-up bar"}],
+      "exercises": [{"id": 4, "name": "Chin-ups", "description": "<p>Palms facing you</p>", "language": 2}]
+    },
+    {
+      "id": 400,
+      "equipment": [{"id": 3, "name": "Dumbbell"}],
+      "translations": [{"id": 5, "name": "Dumbbell Curl", "description": "", "language": 2}]
+    },
+    {
+      "id": 500,
+      "equipment": [],
+      "translations": [{"id": 6, "name": "Crunches", "description": "", "language": 2}]
+    },
+    {
+      "id": 600,
+      "equipment": [{"id": 1, "name": "Barbell"}],
+      "translations": [{"id": 7, "name": "Sumpfhocke", "description": "", "language": 1}]
+    }
+  ]
+}"#;
+
+    #[test]
+    fn read_catalog_ok() {
+        let catalog = read_catalog(PAGE.as_bytes(), None).unwrap();
+        assert_eq!(
+            catalog.parsed.exercises,
+            vec![
+                Exercise {
+                    id: None,
+                    name: "Bench Press".to_string(),
+                    description: Some(
+                        "Lower the bar to the chest, then press. Keep feet flat".to_string()
+                    ),
+                    exercise_type: Barbell,
+                    bar_weight: None,
+                    load_increment: None,
+                },
+                Exercise {
+                    id: None,
+                    name: "Kettlebell Swings".to_string(),
+                    description: None,
+                    exercise_type: KettleBell,
+                    bar_weight: None,
+                    load_increment: None,
+                },
+                Exercise {
+                    id: None,
+                    name: "Chin-ups".to_string(),
+                    description: Some("Palms facing you".to_string()),
+                    exercise_type: BodyWeight,
+                    bar_weight: None,
+                    load_increment: None,
+                },
+            ]
+        );
+        assert!(catalog.parsed.errors.is_empty());
+        assert_eq!(catalog.unsupported, 3);
+    }
+
+    #[test]
+    fn read_catalog_default_type() {
+        let catalog = read_catalog(PAGE.as_bytes(), Some(BodyWeight)).unwrap();
+        assert_eq!(catalog.parsed.exercises.len(), 4);
+        assert_eq!(catalog.parsed.exercises[3].name, "Crunches");
+        assert_eq!(catalog.unsupported, 2);
+    }
+
+    #[test]
+    fn read_catalog_invalid_json() {
+        let result = read_catalog("[1, 2]".as_bytes(), None);
+        assert!(matches!(result.err().unwrap(), TransferError::JsonError(_)))
+    }
+
+    #[test]
+    fn plain_text_ok() {
+        assert_eq!(
+            plain_text("<p>Squat &amp; stand</p><p>Repeat</p>"),
+            "Squat & stand Repeat"
+        );
+        assert_eq!(plain_text(""), "");
+    }
+}
@@ -0,0 +1,225 @@
+use crate::exercise_csv::ParsedExercises;
+use crate::names::NameMapping;
+use crate::{RowError, TransferResult};
+use api::{Exercise, ExerciseType, WeightUnit};
+use std::collections::HashSet;
+use std::io::Read;
+use std::str::FromStr;
+
+pub use api::KG_PER_LB;
+
+/// A single logged set read from another app's export.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImportedSet {
+    /// Line of the source file the set was read from
+    pub line: u64,
+
+    /// Start of the workout as written by the source app
+    pub performed_at: String,
+
+    pub workout: Option<String>,
+    pub exercise: String,
+
+    /// Equipment the source app recorded for the exercise, if any
+    pub exercise_type: Option<ExerciseType>,
+
+    /// Always in kilograms, converted from [`ImportedSet::weight_unit`] when needed
+    pub weight_kg: Option<f64>,
+
+    /// Unit the source app recorded the weight in
+    pub weight_unit: WeightUnit,
+
+    pub reps: Option<u32>,
+}
+
+/// The result of reading a workout export.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ParsedWorkouts {
+    pub sets: Vec<ImportedSet>,
+    pub errors: Vec<RowError>,
+}
+
+/// Reads the workout export of another app, e.g. [`crate::hevy::HevyImporter`].
+pub trait WorkoutImporter {
+    /// Name of the app, used in messages
+    fn source(&self) -> &'static str;
+
+    /// Reads every set of `input`.  Rows that can't be converted are reported in
+    /// [`ParsedWorkouts::errors`]; only an unreadable file or missing columns fail the read.
+    fn read(&self, input: &mut dyn Read) -> TransferResult<ParsedWorkouts>;
+}
+
+impl ParsedWorkouts {
+    /// Number of sets whose weight was converted from another unit to kilograms.
+    pub fn converted(&self) -> usize {
+        self.sets
+            .iter()
+            .filter(|s| s.weight_kg.is_some() && s.weight_unit != WeightUnit::Kg)
+            .count()
+    }
+
+    /// The distinct exercises of the sets, in order of first appearance.  Names are translated
+    /// through `names`, and the type comes from the mapping, then the source app and finally
+    /// `default_type`.  Exercises without a type are reported against the first line they appear
+    /// on.  The result is [`ParsedExercises::create_only`], so the type only applies to the
+    /// exercises the import creates.
+    pub fn exercises(
+        &self,
+        names: &NameMapping,
+        default_type: Option<ExerciseType>,
+    ) -> ParsedExercises {
+        let mut parsed = ParsedExercises {
+            exercises: vec![],
+            errors: self.errors.clone(),
+            has_descriptions: false,
+            create_only: true,
+        };
+        let mut seen = HashSet::new();
+
+        for set in &self.sets {
+            let mapped = names.get(&set.exercise);
+            let name = mapped.map_or(set.exercise.as_str(), |m| m.name.as_str());
+            if !seen.insert(name.to_lowercase()) {
+                continue;
+            }
+
+            let exercise_type = mapped
+                .and_then(|m| m.exercise_type)
+                .or(set.exercise_type)
+                .or(default_type);
+            match exercise_type {
+                Some(exercise_type) => parsed.exercises.push(Exercise {
+                    id: None,
+                    name: name.to_string(),
+                    description: None,
+                    exercise_type,
+                    bar_weight: None,
+                    load_increment: None,
+                }),
+                None => parsed.errors.push(RowError {
+                    line: set.line,
+                    message: format!("missing exercise type for '{}'", name),
+                }),
+            }
+        }
+
+        parsed.errors.sort_by_key(|e| e.line);
+        parsed
+    }
+}
+
+/// Position of `column` in `headers`, ignoring case.
+pub(crate) fn column(headers: &csv::StringRecord, column: &str) -> Option<usize> {
+    headers.iter().position(|h| h.eq_ignore_ascii_case(column))
+}
+
+/// Parses an optional numeric field, where an empty field is `None`.
+pub(crate) fn number<T: FromStr>(value: Option<&str>, field: &str) -> Result<Option<T>, String> {
+    match value.filter(|v| !v.is_empty()) {
+        None => Ok(None),
+        Some(v) => v
+            .parse()
+            .map(Some)
+            .map_err(|_| format!("invalid {} '{}'", field, v)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use api::ExerciseType::{Barbell, BodyWeight, KettleBell};
+
+    fn set(line: u64, exercise: &str, exercise_type: Option<ExerciseType>) -> ImportedSet {
+        ImportedSet {
+            line,
+            performed_at: "2024-05-01 07:00".to_string(),
+            workout: None,
+            exercise: exercise.to_string(),
+            exercise_type,
+            weight_kg: Some(100.0),
+            weight_unit: WeightUnit::Kg,
+            reps: Some(5),
+        }
+    }
+
+    fn exercise(name: &str, exercise_type: ExerciseType) -> Exercise {
+        Exercise {
+            id: None,
+            name: name.to_string(),
+            description: None,
+            exercise_type,
+            bar_weight: None,
+            load_increment: None,
+        }
+    }
+
+    #[test]
+    fn exercises_ok() {
+        let workouts = ParsedWorkouts {
+            sets: vec![
+                set(2, "Deadlift (Barbell)", Some(Barbell)),
+                set(3, "Deadlift (Barbell)", Some(Barbell)),
+                set(4, "Kettlebell Swing", None),
+                set(5, "Pull Up", None),
+                set(6, "Plank", None),
+            ],
+            errors: vec![RowError {
+                line: 7,
+                message: "invalid reps 'x'".to_string(),
+            }],
+        };
+        let mut names = NameMapping::default();
+        names.insert("Deadlift (Barbell)", "Deadlift", None);
+        names.insert("kettlebell swing", "Swing", Some(KettleBell));
+
+        let parsed = workouts.exercises(&names, None);
+        assert_eq!(
+            parsed.exercises,
+            vec![exercise("Deadlift", Barbell), exercise("Swing", KettleBell)]
+        );
+        assert_eq!(
+            parsed
+                .errors
+                .iter()
+                .map(|e| e.to_string())
+                .collect::<Vec<_>>(),
+            vec![
+                "line 5: missing exercise type for 'Pull Up'",
+                "line 6: missing exercise type for 'Plank'",
+                "line 7: invalid reps 'x'",
+            ]
+        );
+
+        let parsed = workouts.exercises(&names, Some(BodyWeight));
+        assert_eq!(parsed.exercises.len(), 4);
+        assert_eq!(parsed.exercises[3], exercise("Plank", BodyWeight));
+    }
+
+    #[test]
+    fn converted_ok() {
+        let mut workouts = ParsedWorkouts {
+            sets: vec![
+                set(2, "Squat", None),
+                set(3, "Squat", None),
+                set(4, "Squat", None),
+            ],
+            errors: vec![],
+        };
+        assert_eq!(workouts.converted(), 0);
+        workouts.sets[0].weight_unit = WeightUnit::Lb;
+        workouts.sets[1].weight_unit = WeightUnit::Lb;
+        workouts.sets[1].weight_kg = None;
+        assert_eq!(workouts.converted(), 1);
+    }
+
+    #[test]
+    fn number_ok() {
+        assert_eq!(number::<f64>(Some("102.5"), "weight"), Ok(Some(102.5)));
+        assert_eq!(number::<u32>(Some(""), "reps"), Ok(None));
+        assert_eq!(number::<u32>(None, "reps"), Ok(None));
+        assert_eq!(
+            number::<u32>(Some("five"), "reps"),
+            Err("invalid reps 'five'".to_string())
+        );
+    }
+}
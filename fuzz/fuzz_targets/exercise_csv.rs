@@ -0,0 +1,16 @@
+#![no_main]
+
+use api::ExerciseType;
+use libfuzzer_sys::fuzz_target;
+use transfer::exercise_csv::{read_exercises, ColumnMapping};
+
+fuzz_target!(|data: &[u8]| {
+    let _ = read_exercises(data, &ColumnMapping::default());
+    let _ = read_exercises(
+        data,
+        &ColumnMapping {
+            default_type: Some(ExerciseType::Barbell),
+            ..Default::default()
+        },
+    );
+});
@@ -0,0 +1,20 @@
+#![no_main]
+
+use api::{Exercise, ExerciseAlias, Weight};
+use libfuzzer_sys::fuzz_target;
+use transfer::matcher::{normalize, ExerciseMatcher};
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(exercise) = serde_json::from_slice::<Exercise>(data) {
+        // Whatever deserializes has to serialize back
+        serde_json::to_vec(&exercise).unwrap();
+        let exercises = [exercise];
+        let _ = ExerciseMatcher::new(&exercises, &[]).resolve(&exercises[0].name);
+    }
+    let _ = serde_json::from_slice::<Vec<ExerciseAlias>>(data);
+
+    if let Ok(text) = std::str::from_utf8(data) {
+        let _ = text.parse::<Weight>();
+        let _ = normalize(text);
+    }
+});
@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use transfer::names::NameMapping;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = NameMapping::read(data);
+});
@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use transfer::wger::read_catalog;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = read_catalog(data, None);
+});
@@ -0,0 +1,17 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use transfer::fitnotes::FitNotesImporter;
+use transfer::hevy::HevyImporter;
+use transfer::names::NameMapping;
+use transfer::workout::WorkoutImporter;
+
+fuzz_target!(|data: &[u8]| {
+    let importers: [&dyn WorkoutImporter; 2] = [&HevyImporter, &FitNotesImporter];
+    for importer in importers {
+        if let Ok(workouts) = importer.read(&mut &data[..]) {
+            let _ = workouts.converted();
+            let _ = workouts.exercises(&NameMapping::default(), None);
+        }
+    }
+});